@@ -0,0 +1,63 @@
+mod common;
+
+use asc_kernel_runtime::Runtime;
+use asc_types::{model::KernelInput, ReasonCode, Verdict};
+
+fn level_input(bank_deg: f64, pitch_deg: f64) -> KernelInput {
+    let mut input = common::nominal_input(1, 0);
+    input.state.bank_deg = bank_deg;
+    input.state.pitch_deg = pitch_deg;
+    input
+}
+
+#[test]
+fn pitch_beyond_attitude_limit_is_state_out_of_bounds() {
+    let mut runtime = Runtime::new("fingerprint".into()).expect("runtime");
+    let out = runtime.evaluate(&level_input(0.0, -85.0));
+    assert_eq!(out.verdict, Verdict::Hold);
+    assert_eq!(out.reasons, vec![ReasonCode::StateOutOfBounds]);
+}
+
+#[test]
+fn combined_attitude_magnitude_trips_soft_limit_before_bank_invariant() {
+    let mut runtime = Runtime::new("fingerprint".into()).expect("runtime");
+    let out = runtime.evaluate(&level_input(59.0, 75.0));
+    assert_eq!(out.verdict, Verdict::Hold);
+    assert!(out.reasons.contains(&ReasonCode::StateOutOfBounds));
+    assert!(!out.reasons.contains(&ReasonCode::InvariantViolation));
+}
+
+#[test]
+fn moderate_combined_attitude_is_allowed() {
+    let mut runtime = Runtime::new("fingerprint".into()).expect("runtime");
+    let out = runtime.evaluate(&level_input(40.0, 30.0));
+    assert_eq!(out.verdict, Verdict::Allow);
+    assert!(out.reasons.is_empty());
+}
+
+#[test]
+fn bank_beyond_hard_invariant_also_trips_the_soft_limit_and_shuts_down() {
+    let mut runtime = Runtime::new("fingerprint".into()).expect("runtime");
+    let out = runtime.evaluate(&level_input(65.0, 0.0));
+    assert_eq!(out.verdict, Verdict::Shutdown);
+    // Past the bank invariant the barrier also demands a recovering roll rate.
+    assert_eq!(
+        out.reasons,
+        vec![
+            ReasonCode::StateOutOfBounds,
+            ReasonCode::InvariantViolation,
+            ReasonCode::BarrierProjection
+        ]
+    );
+}
+
+#[test]
+fn missing_pitch_deserializes_as_level() {
+    let mut value = serde_json::to_value(level_input(10.0, 0.0)).expect("serialize input");
+    value["state"]
+        .as_object_mut()
+        .expect("state object")
+        .remove("pitch_deg");
+    let input: KernelInput = serde_json::from_value(value).expect("input without pitch");
+    assert_eq!(input.state.pitch_deg, 0.0);
+}
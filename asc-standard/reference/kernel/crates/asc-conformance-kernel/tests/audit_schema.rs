@@ -0,0 +1,119 @@
+mod common;
+
+use asc_kernel_runtime::Runtime;
+use asc_logging::{write_audit, AuditRecord, EventPayload, AUDIT_FIELDS, AUDIT_SCHEMA_VERSION};
+use serde::{
+    de::{IgnoredAny, MapAccess, Visitor},
+    Deserialize, Deserializer,
+};
+use std::{fmt, fs};
+
+#[derive(Deserialize)]
+struct AuditSchema {
+    schema_version: u32,
+    fields: Vec<String>,
+}
+
+// Top-level keys of a JSON object, in the order they were emitted.
+struct Keys(Vec<String>);
+
+impl<'de> Deserialize<'de> for Keys {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct KeysVisitor;
+
+        impl<'de> Visitor<'de> for KeysVisitor {
+            type Value = Keys;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("an object")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Keys, A::Error> {
+                let mut keys = Vec::new();
+                while let Some(key) = map.next_key()? {
+                    map.next_value::<IgnoredAny>()?;
+                    keys.push(key);
+                }
+                Ok(Keys(keys))
+            }
+        }
+
+        deserializer.deserialize_map(KeysVisitor)
+    }
+}
+
+fn declared_schema() -> AuditSchema {
+    let raw = fs::read_to_string(common::repo_root().join("spec/asc/audit-log-schema.yaml"))
+        .expect("read audit schema");
+    serde_yaml::from_str(&raw).expect("parse audit schema")
+}
+
+// Fails when spec/asc/audit-log-schema.yaml changes without regenerating the
+// audit record, or when the emitted records stop matching it.
+#[test]
+fn emitted_audit_records_match_the_declared_schema() {
+    let schema = declared_schema();
+    assert_eq!(AUDIT_SCHEMA_VERSION, schema.schema_version);
+    assert_eq!(AUDIT_FIELDS.as_slice(), schema.fields.as_slice());
+
+    let mut runtime =
+        Runtime::from_repo(&common::repo_root(), "uas-small").expect("runtime from repo");
+    runtime.evaluate(&common::sample_input(1));
+    runtime.evaluate(&common::sample_input(3));
+    let mut input = common::sample_input(4);
+    input.state.bank_deg = 75.0;
+    runtime.evaluate(&input);
+
+    let mut out = Vec::new();
+    let written = write_audit(&runtime.log.records, &mut out).expect("write audit");
+    let lines: Vec<_> = std::str::from_utf8(&out)
+        .expect("utf-8 audit")
+        .lines()
+        .collect();
+    assert_eq!(written, 3);
+    assert_eq!(lines.len(), written);
+
+    let mut expected_keys = vec!["schema_version".to_string()];
+    expected_keys.extend(schema.fields);
+    for line in &lines {
+        let Keys(keys) = serde_json::from_str(line).expect("audit record is an object");
+        assert_eq!(keys, expected_keys, "{line}");
+        let value: serde_json::Value = serde_json::from_str(line).expect("parse audit record");
+        assert_eq!(value["schema_version"], schema.schema_version);
+    }
+}
+
+#[test]
+fn audit_records_summarize_evaluation_records() {
+    let mut runtime = Runtime::new("fingerprint".into()).expect("runtime");
+    runtime.evaluate(&common::sample_input(1));
+    runtime.evaluate(&common::sample_input(3));
+
+    let audits: Vec<(u64, AuditRecord)> = runtime
+        .log
+        .records
+        .iter()
+        .filter_map(|record| record.audit().map(|audit| (record.seq, audit)))
+        .collect();
+    assert_eq!(audits.len(), 2);
+    for (record, (seq, audit)) in runtime
+        .log
+        .records
+        .iter()
+        .filter(|r| matches!(r.payload, EventPayload::Evaluation(_)))
+        .zip(&audits)
+    {
+        let output = record.payload.output().expect("evaluation output");
+        assert_eq!(audit.tick.seq, *seq);
+        assert_eq!(audit.tip_hash, record.hash);
+        assert_eq!(audit.verdict, output.verdict);
+        assert_eq!(audit.reasons, output.reasons);
+        assert_eq!(audit.contract_fingerprint.as_str(), "fingerprint");
+    }
+    // The skipped seq is logged as a runtime event, which is not audited.
+    assert!(runtime.log.records.len() > audits.len());
+
+    let line = serde_json::to_string(&audits[1].1).expect("serialize audit record");
+    let parsed: AuditRecord = serde_json::from_str(&line).expect("parse audit record");
+    assert_eq!(parsed.tip_hash, runtime.tip_hash());
+}
@@ -0,0 +1,65 @@
+mod common;
+
+use asc_kernel_model::Limits;
+use asc_kernel_runtime::Runtime;
+use asc_types::{model::KernelInput, ReasonCode, Verdict};
+
+fn input(altitude_m: f64, bank_deg: f64, roll_rate_dps: f64, climb_mps: f64) -> KernelInput {
+    let mut input = common::nominal_input(1, 0);
+    input.state.position_m = [0.0, 0.0, -altitude_m];
+    input.state.bank_deg = bank_deg;
+    input.intent.desired_rates_dps = [roll_rate_dps, 0.0, 0.0];
+    input.intent.desired_climb_mps = climb_mps;
+    input
+}
+
+#[test]
+fn descent_is_limited_by_altitude_margin() {
+    let limits = Limits::baked();
+    let margin_m = 1.0;
+    let altitude_m = limits.invariants.min_altitude_m + margin_m;
+    let mut runtime = Runtime::new("fingerprint".into()).expect("runtime");
+    let out = runtime.evaluate(&input(altitude_m, 0.0, 0.0, -3.0));
+    assert_eq!(out.verdict, Verdict::Clamp);
+    assert_eq!(out.reasons, vec![ReasonCode::BarrierProjection]);
+    assert_eq!(
+        out.command.applied_climb_mps,
+        -limits.invariants.barrier_gain_per_s * margin_m
+    );
+}
+
+#[test]
+fn descent_within_barrier_is_untouched() {
+    let mut runtime = Runtime::new("fingerprint".into()).expect("runtime");
+    let out = runtime.evaluate(&input(20.0, 0.0, 0.0, -3.0));
+    assert_eq!(out.verdict, Verdict::Allow);
+    assert_eq!(out.command.applied_climb_mps, -3.0);
+}
+
+#[test]
+fn roll_toward_bank_limit_is_limited_by_bank_margin() {
+    let limits = Limits::baked();
+    let bank_deg = limits.invariants.max_bank_deg - 10.0;
+    let mut runtime = Runtime::new("fingerprint".into()).expect("runtime");
+    let out = runtime.evaluate(&input(20.0, bank_deg, 30.0, 0.0));
+    assert_eq!(out.verdict, Verdict::Clamp);
+    assert_eq!(out.reasons, vec![ReasonCode::BarrierProjection]);
+    assert_eq!(
+        out.command.applied_rates_dps[0],
+        limits.invariants.barrier_gain_per_s * 10.0
+    );
+
+    let mut runtime = Runtime::new("fingerprint".into()).expect("runtime");
+    let out = runtime.evaluate(&input(20.0, bank_deg, -30.0, 0.0));
+    assert_eq!(out.verdict, Verdict::Allow);
+    assert_eq!(out.command.applied_rates_dps[0], -30.0);
+}
+
+#[test]
+fn barrier_projection_keeps_within_flow_limits() {
+    let limits = Limits::baked();
+    let mut runtime = Runtime::new("fingerprint".into()).expect("runtime");
+    let altitude_m = limits.invariants.min_altitude_m + 0.5;
+    let out = runtime.evaluate(&input(altitude_m, 0.0, 0.0, -100.0));
+    assert!(out.command.applied_climb_mps.abs() <= limits.flow.max_climb_rate_mps);
+}
@@ -0,0 +1,173 @@
+mod common;
+
+use asc_kernel_runtime::Runtime;
+use asc_types::{
+    model::{FlightPhase, KernelInput},
+    ReasonCode, Verdict,
+};
+
+fn repo_runtime(profile: &str) -> Runtime {
+    Runtime::from_repo(&common::repo_root(), profile).expect("runtime from repo")
+}
+
+fn input(velocity_mps: f64, payload_kg: f64, climb_mps: f64) -> KernelInput {
+    let mut input = common::nominal_input(1, 0);
+    input.state.velocity_mps = velocity_mps;
+    input.state.airspeed_mps = Some(velocity_mps);
+    input.state.payload_kg = payload_kg;
+    input.intent.desired_climb_mps = climb_mps;
+    input
+}
+
+#[test]
+fn payload_above_capability_holds() {
+    let mut runtime = Runtime::new("fingerprint".into()).expect("runtime");
+    let max_payload_kg = runtime.limits().capabilities.max_payload_kg;
+    let out = runtime.evaluate(&input(20.0, max_payload_kg + 0.5, 0.0));
+    assert_eq!(out.verdict, Verdict::Hold);
+    assert_eq!(out.reasons, vec![ReasonCode::PayloadExceeded]);
+}
+
+#[test]
+fn fixed_wing_below_stall_speed_overrides() {
+    let mut runtime = repo_runtime("fixed-wing");
+    let out = runtime.evaluate(&input(10.0, 0.0, 0.0));
+    assert_eq!(out.verdict, Verdict::Override);
+    assert_eq!(out.reasons, vec![ReasonCode::AirspeedBelowStall]);
+
+    // A stalling aircraft lowers its nose and descends rather than taking the
+    // profile's climbing Override command.
+    let stall = runtime.limits().fallbacks.stall;
+    assert!(stall.climb_mps < 0.0);
+    assert_eq!(out.command.applied_rates_dps, stall.rates_dps);
+    assert_eq!(out.command.applied_climb_mps, stall.climb_mps);
+
+    let mut runtime = repo_runtime("fixed-wing");
+    let out = runtime.evaluate(&input(20.0, 0.0, 0.0));
+    assert_eq!(out.verdict, Verdict::Allow);
+}
+
+#[test]
+fn stall_check_uses_airspeed_and_skips_ground_phases() {
+    // Fast over the ground but slow through the air.
+    let mut tick = input(20.0, 0.0, 0.0);
+    tick.state.airspeed_mps = Some(10.0);
+    let out = repo_runtime("fixed-wing").evaluate(&tick);
+    assert_eq!(out.reasons, vec![ReasonCode::AirspeedBelowStall]);
+
+    tick.state.airspeed_mps = None;
+    let out = repo_runtime("fixed-wing").evaluate(&tick);
+    assert_eq!(out.reasons, vec![ReasonCode::AirspeedBelowStall]);
+
+    for phase in [
+        FlightPhase::Ground,
+        FlightPhase::Takeoff,
+        FlightPhase::Landing,
+    ] {
+        let mut tick = input(0.0, 0.0, 0.0);
+        tick.state.position_m[2] = 0.0;
+        tick.state.phase = phase;
+        tick.state.airspeed_mps = Some(0.0);
+        let out = repo_runtime("fixed-wing").evaluate(&tick);
+        assert!(
+            !out.reasons.contains(&ReasonCode::AirspeedBelowStall),
+            "{phase:?}"
+        );
+    }
+
+    // A ground phase reported in flight is not plausible and keeps the check.
+    let mut tick = input(0.0, 0.0, 0.0);
+    tick.state.phase = FlightPhase::Ground;
+    tick.state.airspeed_mps = Some(0.0);
+    let out = repo_runtime("fixed-wing").evaluate(&tick);
+    assert!(out.reasons.contains(&ReasonCode::AirspeedBelowStall));
+}
+
+#[test]
+fn fixed_wing_hold_loiters() {
+    let mut runtime = repo_runtime("fixed-wing");
+    let out = runtime.evaluate(&input(20.0, 6.0, 0.0));
+    assert_eq!(out.verdict, Verdict::Hold);
+    let loiter = runtime.limits().fallbacks.loiter;
+    assert_eq!(out.command.applied_rates_dps, loiter.rates_dps);
+    assert!(out
+        .command
+        .applied_rates_dps
+        .iter()
+        .any(|rate| *rate != 0.0));
+}
+
+#[test]
+fn hover_descent_is_limited() {
+    let mut runtime = repo_runtime("hybrid-vtol");
+    let max_descent_mps = runtime
+        .limits()
+        .capabilities
+        .max_hover_descent_mps
+        .expect("hybrid-vtol hovers");
+    let out = runtime.evaluate(&input(0.0, 0.0, -(max_descent_mps + 1.0)));
+    assert_eq!(out.verdict, Verdict::Clamp);
+    assert_eq!(out.reasons, vec![ReasonCode::FlowConstraintViolation]);
+    assert_eq!(out.command.applied_climb_mps, -max_descent_mps);
+}
+
+#[test]
+fn hybrid_vtol_decides_wingborne_flight_on_airspeed() {
+    // Slow over the ground into a headwind, but wingborne.
+    let mut runtime = repo_runtime("hybrid-vtol");
+    let max_descent_mps = runtime
+        .limits()
+        .capabilities
+        .max_hover_descent_mps
+        .expect("hybrid-vtol hovers");
+    let mut tick = input(0.0, 0.0, -(max_descent_mps + 1.0));
+    tick.state.airspeed_mps = Some(20.0);
+    let out = runtime.evaluate(&tick);
+    assert_eq!(out.verdict, Verdict::Allow);
+
+    let mut tick = input(0.0, 6.0, 0.0);
+    tick.state.airspeed_mps = Some(20.0);
+    let out = repo_runtime("hybrid-vtol").evaluate(&tick);
+    assert_eq!(out.verdict, Verdict::Hold);
+    let loiter = runtime.limits().fallbacks.loiter;
+    assert_eq!(out.command.applied_rates_dps, loiter.rates_dps);
+
+    // Fast over the ground downwind, or without an airspeed, it hovers.
+    for airspeed_mps in [Some(0.0), None] {
+        let mut tick = input(20.0, 0.0, -(max_descent_mps + 1.0));
+        tick.state.airspeed_mps = airspeed_mps;
+        let out = repo_runtime("hybrid-vtol").evaluate(&tick);
+        assert_eq!(out.verdict, Verdict::Clamp, "{airspeed_mps:?}");
+        assert_eq!(out.command.applied_climb_mps, -max_descent_mps);
+    }
+}
+
+#[test]
+fn hybrid_vtol_limits_follow_airspeed() {
+    // A 3.5 m/s descent lies between the hover descent limit (2.5 m/s) and the
+    // hover climb rate limit (4.0 m/s), so in hover it trips the descent limit
+    // alone, and it is within the forward-flight limits.
+    let mut runtime = repo_runtime("hybrid-vtol");
+    let out = runtime.evaluate(&input(0.0, 4.5, -3.5));
+    assert_eq!(out.verdict, Verdict::Hold);
+    assert_eq!(
+        out.reasons,
+        vec![
+            ReasonCode::FlowConstraintViolation,
+            ReasonCode::PayloadExceeded
+        ]
+    );
+    assert_eq!(
+        out.command.applied_rates_dps,
+        runtime.limits().fallbacks.hold.rates_dps
+    );
+
+    let mut runtime = repo_runtime("hybrid-vtol");
+    let out = runtime.evaluate(&input(20.0, 4.5, -3.5));
+    assert_eq!(out.verdict, Verdict::Hold);
+    assert_eq!(out.reasons, vec![ReasonCode::PayloadExceeded]);
+    assert_eq!(
+        out.command.applied_rates_dps,
+        runtime.limits().fallbacks.loiter.rates_dps
+    );
+}
@@ -0,0 +1,152 @@
+mod common;
+
+use asc_kernel_runtime::Runtime;
+use asc_logging::{
+    verify_chain, verify_dir, BreakKind, EventLog, EventPayload, EventRecord, StoreConfig,
+};
+use asc_types::Verdict;
+use std::fs;
+
+fn recorded(seqs: &[u64]) -> (Vec<EventRecord>, String) {
+    let mut runtime = Runtime::new("fingerprint".into()).expect("runtime");
+    for seq in seqs {
+        runtime.evaluate(&common::sample_input(*seq));
+    }
+    (runtime.log.records.clone(), runtime.tip_hash())
+}
+
+#[test]
+fn intact_chain_verifies_against_its_tip() {
+    let (records, tip) = recorded(&[1, 2, 3, 4, 5]);
+    let report = verify_chain(&records, Some(&tip));
+    assert!(report.is_intact(), "{report:?}");
+    assert_eq!(report.records, 5);
+    assert_eq!(report.tip_hash, tip);
+}
+
+#[test]
+fn announced_out_of_order_tick_is_not_a_break() {
+    let (records, tip) = recorded(&[1, 2, 3, 2, 4]);
+    let report = verify_chain(&records, Some(&tip));
+    assert!(report.is_intact(), "{report:?}");
+}
+
+#[test]
+fn modified_payload_is_reported_at_its_index() {
+    let (mut records, tip) = recorded(&[1, 2, 3, 4, 5]);
+    let EventPayload::Evaluation(evaluation) = &mut records[2].payload else {
+        panic!("evaluation record");
+    };
+    evaluation.output.verdict = Verdict::Shutdown;
+
+    let brk = verify_chain(&records, Some(&tip))
+        .first_break
+        .expect("break found");
+    assert_eq!(brk.kind, BreakKind::ModifiedPayload);
+    assert_eq!(brk.index, 2);
+    assert_eq!(brk.seq, Some(3));
+}
+
+#[test]
+fn deleted_record_is_reported_where_the_link_breaks() {
+    let (mut records, tip) = recorded(&[1, 2, 3, 4, 5]);
+    records.remove(2);
+
+    let report = verify_chain(&records, Some(&tip));
+    let brk = report.first_break.expect("break found");
+    assert_eq!(brk.kind, BreakKind::DeletedRecord);
+    assert_eq!(brk.index, 2);
+    assert_eq!(report.tip_hash, records[1].hash);
+}
+
+#[test]
+fn reordered_records_are_reported() {
+    let (mut records, tip) = recorded(&[1, 2, 3, 4, 5]);
+    records.swap(2, 3);
+    let brk = verify_chain(&records, Some(&tip))
+        .first_break
+        .expect("break found");
+    assert_eq!(brk.kind, BreakKind::ReorderedRecord);
+    assert_eq!(brk.index, 2);
+
+    // Re-chained records still cannot go back in seq unannounced.
+    let (records, _) = recorded(&[1, 2]);
+    let mut log = EventLog::default();
+    for record in records.iter().rev() {
+        let EventPayload::Evaluation(evaluation) = &record.payload else {
+            panic!("evaluation record");
+        };
+        log.append(record.seq, (**evaluation).clone());
+    }
+    let brk = verify_chain(&log.records, Some(&log.tip_hash))
+        .first_break
+        .expect("break found");
+    assert_eq!(brk.kind, BreakKind::ReorderedRecord);
+    assert_eq!(brk.index, 1);
+}
+
+#[test]
+fn forged_or_truncated_tip_is_reported() {
+    let (mut records, tip) = recorded(&[1, 2, 3, 4, 5]);
+    let forged = "0".repeat(64);
+    let brk = verify_chain(&records, Some(&forged))
+        .first_break
+        .expect("break found");
+    assert_eq!(brk.kind, BreakKind::ForgedTip);
+    assert_eq!(brk.index, 5);
+
+    let anchored = records[2].hash.clone();
+    let brk = verify_chain(&records, Some(&anchored))
+        .first_break
+        .expect("break found");
+    assert_eq!(brk.kind, BreakKind::ForgedTip);
+    assert_eq!(brk.index, 3);
+
+    records.pop();
+    let report = verify_chain(&records, Some(&tip));
+    assert_eq!(
+        report.first_break.map(|b| (b.kind, b.index)),
+        Some((BreakKind::ForgedTip, 4))
+    );
+}
+
+#[test]
+fn persistent_log_is_verified_from_disk() {
+    let dir = std::env::temp_dir().join(format!("asc-chain-verify-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    let config = StoreConfig {
+        segment_max_bytes: 4096,
+        ..StoreConfig::new(&dir)
+    };
+    let mut runtime = Runtime::new("fingerprint".into())
+        .expect("runtime")
+        .with_log(EventLog::open(config).expect("open log"))
+        .expect("resume log");
+    for seq in 1..=6 {
+        runtime.evaluate(&common::sample_input(seq));
+    }
+    let tip = runtime.tip_hash();
+    drop(runtime);
+
+    let report = verify_dir(&dir, Some(&tip)).expect("verify log");
+    assert!(report.is_intact(), "{report:?}");
+    assert_eq!(report.records, 6);
+    let manifest = serde_json::to_value(&report).expect("serialize report");
+    assert_eq!(manifest["first_break"], serde_json::Value::Null);
+
+    let mut segments: Vec<_> = fs::read_dir(&dir)
+        .expect("read log dir")
+        .map(|entry| entry.expect("dir entry").path())
+        .collect();
+    segments.sort();
+    assert!(segments.len() > 1);
+    let contents = fs::read_to_string(&segments[1]).expect("read segment");
+    fs::write(&segments[1], contents.replacen("\"Allow\"", "\"Hold\"", 1)).expect("write segment");
+
+    let report = verify_dir(&dir, Some(&tip)).expect("verify log");
+    let brk = report.first_break.expect("break found");
+    assert_eq!(brk.kind, BreakKind::ModifiedPayload);
+    let first_segment = fs::read_to_string(&segments[0]).expect("read segment");
+    assert_eq!(brk.index, first_segment.lines().count());
+    fs::remove_dir_all(&dir).expect("remove log dir");
+}
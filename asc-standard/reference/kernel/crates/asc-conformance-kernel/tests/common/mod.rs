@@ -0,0 +1,51 @@
+// Helpers shared by the conformance tests. Every test binary compiles its own
+// copy and uses only some of them.
+#![allow(dead_code, unused_imports)]
+
+mod repo;
+
+pub use repo::{repo_root, scratch_repo};
+
+use asc_types::model::{FlightPhase, Intent, KernelInput, ObservedState, Tick};
+
+// A level, armed cruise tick well inside every uas-small limit, holding
+// position with no commanded motion. Tests override the fields they exercise.
+pub fn nominal_input(seq: u64, ts_ms: u64) -> KernelInput {
+    KernelInput {
+        tick: Tick { seq, ts_ms },
+        state: ObservedState {
+            frame: "NED".try_into().expect("frame fits"),
+            position_m: [0.0, 0.0, -20.0],
+            velocity_mps: 0.0,
+            airspeed_mps: Some(20.0),
+            bank_deg: 0.0,
+            pitch_deg: 0.0,
+            soc_percent: 90.0,
+            power_w: 400.0,
+            battery_capacity_wh: 500.0,
+            input_age_ms: 0,
+            armed: true,
+            payload_kg: 0.0,
+            phase: FlightPhase::Cruise,
+        },
+        intent: Intent {
+            desired_rates_dps: [0.0, 0.0, 0.0],
+            desired_climb_mps: 0.0,
+        },
+        extension: Default::default(),
+    }
+}
+
+// A moving, banked tick for the logging tests, so that every recorded field
+// carries a distinct value.
+pub fn sample_input(seq: u64) -> KernelInput {
+    let mut input = nominal_input(seq, seq * 10);
+    input.state.position_m = [10.0, 0.0, -20.0];
+    input.state.velocity_mps = 12.0;
+    input.state.bank_deg = 5.0;
+    input.state.soc_percent = 80.0;
+    input.state.input_age_ms = 10;
+    input.intent.desired_rates_dps = [1.0, 2.0, 3.0];
+    input.intent.desired_climb_mps = 1.5;
+    input
+}
@@ -0,0 +1,36 @@
+// Repo and scratch-tree helpers shared by the conformance tests and
+// asc-contract's unit tests, which include this file by path.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+pub fn repo_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../../../../")
+        .canonicalize()
+        .expect("canonical repo root")
+}
+
+// Copies the spec tree into a fresh scratch repo root for the caller to edit.
+// `name` keeps concurrently running tests apart.
+pub fn scratch_repo(name: &str) -> PathBuf {
+    let scratch = std::env::temp_dir().join(format!("asc-{name}-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&scratch);
+    copy_dir(&repo_root().join("spec"), &scratch.join("spec"));
+    scratch
+}
+
+pub fn copy_dir(from: &Path, to: &Path) {
+    fs::create_dir_all(to).expect("create dir");
+    for entry in fs::read_dir(from).expect("read dir") {
+        let entry = entry.expect("dir entry");
+        let target = to.join(entry.file_name());
+        if entry.file_type().expect("file type").is_dir() {
+            copy_dir(&entry.path(), &target);
+        } else {
+            fs::copy(entry.path(), target).expect("copy file");
+        }
+    }
+}
@@ -1,31 +1,18 @@
+mod common;
+
 use asc_kernel_runtime::Runtime;
-use asc_types::{
-    model::{Intent, KernelInput, ObservedState, Tick},
-    ReasonCode, Verdict,
-};
+use asc_types::{model::KernelInput, ReasonCode, Verdict};
 
 fn sample_input(seq: u64, ts_ms: u64) -> KernelInput {
-    KernelInput {
-        tick: Tick { seq, ts_ms },
-        state: ObservedState {
-            frame: "NED".to_string(),
-            position_m: [10.0, 0.0, 20.0],
-            velocity_mps: 12.0,
-            bank_deg: 5.0,
-            soc_percent: 80.0,
-            input_age_ms: 10,
-        },
-        intent: Intent {
-            desired_rates_dps: [1.0, 2.0, 3.0],
-            desired_climb_mps: 1.5,
-        },
-    }
+    let mut input = common::sample_input(seq);
+    input.tick.ts_ms = ts_ms;
+    input
 }
 
 #[test]
 fn replay_tip_hash_is_deterministic() {
-    let mut run_a = Runtime::new("fingerprint".into());
-    let mut run_b = Runtime::new("fingerprint".into());
+    let mut run_a = Runtime::new("fingerprint".into()).expect("runtime");
+    let mut run_b = Runtime::new("fingerprint".into()).expect("runtime");
 
     for idx in 0..25 {
         let input = sample_input(idx, idx * 20);
@@ -38,7 +25,7 @@ fn replay_tip_hash_is_deterministic() {
 
 #[test]
 fn temporal_violation_and_deadline_miss_are_deterministic() {
-    let mut runtime = Runtime::new("fingerprint".into());
+    let mut runtime = Runtime::new("fingerprint".into()).expect("runtime");
     let _ = runtime.evaluate(&sample_input(1, 0));
 
     let out = runtime.evaluate(&sample_input(2, 150));
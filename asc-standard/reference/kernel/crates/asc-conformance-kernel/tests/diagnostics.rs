@@ -0,0 +1,101 @@
+mod common;
+
+use asc_kernel_model::Limits;
+use asc_kernel_runtime::Runtime;
+use asc_types::{
+    model::{CheckId, CheckOutcome, KernelInput, KernelOutput, Tick},
+    ReasonCode, Severity, Verdict,
+};
+
+fn input(velocity_mps: f64, soc_percent: f64) -> KernelInput {
+    let mut input = common::nominal_input(1, 0);
+    input.state.velocity_mps = velocity_mps;
+    input.state.soc_percent = soc_percent;
+    input
+}
+
+fn diagnostic(out: &KernelOutput, check: CheckId) -> &CheckOutcome {
+    out.diagnostics
+        .iter()
+        .find(|d| d.check == check)
+        .unwrap_or_else(|| panic!("{check:?} not reported"))
+}
+
+#[test]
+fn every_check_is_reported_on_a_nominal_tick() {
+    let mut runtime = Runtime::new("fingerprint".into()).expect("runtime");
+    let out = runtime.evaluate(&input(0.0, 90.0));
+    assert_eq!(out.verdict, Verdict::Allow);
+    assert_eq!(out.diagnostics.len(), 28);
+    assert!(out.diagnostics.iter().all(|d| !d.tripped
+        && d.verdict == Verdict::Allow
+        && d.severity == Severity::Info
+        && d.margin.is_none_or(|m| m >= 0.0)));
+
+    let soc = diagnostic(&out, CheckId::SocFloor);
+    let floor = Limits::baked().energy.min_soc_percent;
+    assert_eq!(soc.measured, Some(90.0));
+    assert_eq!(soc.limit, Some(floor));
+    assert_eq!(soc.margin, Some(90.0 - floor));
+}
+
+#[test]
+fn tripped_check_carries_value_limit_margin_and_severity() {
+    let limits = Limits::baked();
+    let mut runtime = Runtime::new("fingerprint".into()).expect("runtime");
+    let over = limits.state.max_speed_mps + 3.0;
+    let out = runtime.evaluate(&input(over, 90.0));
+    assert_eq!(out.reasons, vec![ReasonCode::StateOutOfBounds]);
+
+    let speed = diagnostic(&out, CheckId::Speed);
+    assert!(speed.tripped);
+    assert_eq!(speed.verdict, Verdict::Clamp);
+    assert_eq!(speed.severity, Severity::Warning);
+    assert_eq!(speed.measured, Some(over));
+    assert_eq!(speed.limit, Some(limits.state.max_speed_mps));
+    assert_eq!(speed.margin, Some(-3.0));
+}
+
+#[test]
+fn reasons_list_only_tripped_checks() {
+    let mut runtime = Runtime::new("fingerprint".into()).expect("runtime");
+    let out = runtime.evaluate(&input(0.0, 18.0));
+    let tripped = out
+        .diagnostics
+        .iter()
+        .filter(|d| d.tripped)
+        .map(|d| d.reason)
+        .collect::<Vec<_>>();
+    assert_eq!(out.reasons, tripped);
+    assert_eq!(
+        diagnostic(&out, CheckId::SocFloor).severity,
+        Severity::Critical
+    );
+}
+
+#[test]
+fn non_finite_measurements_are_omitted() {
+    let mut runtime = Runtime::new("fingerprint".into()).expect("runtime");
+    let out = runtime.evaluate(&input(f64::NAN, 90.0));
+    let speed = diagnostic(&out, CheckId::Speed);
+    assert_eq!(speed.measured, None);
+    assert_eq!(speed.margin, None);
+    assert!(diagnostic(&out, CheckId::InputFinite).tripped);
+}
+
+#[test]
+fn diagnostics_keep_the_hash_chain_deterministic() {
+    let run = || {
+        let mut runtime = Runtime::new("fingerprint".into()).expect("runtime");
+        for seq in 1..=20 {
+            let mut tick = input(seq as f64 * 1.7, 90.0 - seq as f64);
+            tick.tick = Tick {
+                seq,
+                ts_ms: seq * 10,
+            };
+            let _ = runtime.evaluate(&tick);
+        }
+        runtime.tip_hash()
+    };
+    assert_eq!(run(), run());
+}
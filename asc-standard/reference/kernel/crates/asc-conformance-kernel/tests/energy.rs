@@ -0,0 +1,45 @@
+mod common;
+
+use asc_kernel_runtime::Runtime;
+use asc_types::{model::KernelInput, ReasonCode, Verdict};
+
+fn powered_input(soc_percent: f64, power_w: f64, battery_capacity_wh: f64) -> KernelInput {
+    let mut input = common::nominal_input(1, 0);
+    input.state.soc_percent = soc_percent;
+    input.state.power_w = power_w;
+    input.state.battery_capacity_wh = battery_capacity_wh;
+    input
+}
+
+#[test]
+fn projected_endurance_below_reserve_holds() {
+    let mut runtime = Runtime::new("fingerprint".into()).expect("runtime");
+    // 30 Wh remaining at 800 W is 135 s, short of the 180 s reserve.
+    let out = runtime.evaluate(&powered_input(30.0, 800.0, 100.0));
+    assert_eq!(out.verdict, Verdict::Hold);
+    assert_eq!(out.reasons, vec![ReasonCode::EnergyBudgetExceeded]);
+}
+
+#[test]
+fn projected_endurance_above_reserve_is_allowed() {
+    let mut runtime = Runtime::new("fingerprint".into()).expect("runtime");
+    // 30 Wh remaining at 500 W is 216 s.
+    let out = runtime.evaluate(&powered_input(30.0, 500.0, 100.0));
+    assert_eq!(out.verdict, Verdict::Allow);
+    assert!(out.reasons.is_empty());
+}
+
+#[test]
+fn idle_power_draw_does_not_project_endurance() {
+    let mut runtime = Runtime::new("fingerprint".into()).expect("runtime");
+    let out = runtime.evaluate(&powered_input(30.0, 0.0, 100.0));
+    assert_eq!(out.verdict, Verdict::Allow);
+}
+
+#[test]
+fn power_draw_above_limit_is_flagged() {
+    let mut runtime = Runtime::new("fingerprint".into()).expect("runtime");
+    let out = runtime.evaluate(&powered_input(90.0, 2600.0, 500.0));
+    assert_eq!(out.verdict, Verdict::Clamp);
+    assert_eq!(out.reasons, vec![ReasonCode::PowerLimitExceeded]);
+}
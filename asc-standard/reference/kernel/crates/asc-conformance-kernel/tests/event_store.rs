@@ -0,0 +1,257 @@
+mod common;
+
+use asc_kernel_runtime::Runtime;
+use asc_logging::{
+    read_records, EventLog, EventPayload, FsyncPolicy, RuntimeEvent, StoreConfig,
+    MAX_PENDING_RECORDS,
+};
+use asc_types::{ReasonCode, Verdict};
+use std::{
+    fs::{self, OpenOptions},
+    io::{ErrorKind, Write},
+    path::{Path, PathBuf},
+};
+
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("asc-{name}-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+fn persistent_runtime(config: StoreConfig) -> Runtime {
+    Runtime::new("fingerprint".into())
+        .expect("runtime")
+        .with_log(EventLog::open(config).expect("open log"))
+        .expect("resume log")
+}
+
+fn segment_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<_> = fs::read_dir(dir)
+        .expect("read log dir")
+        .map(|entry| entry.expect("dir entry").path())
+        .collect();
+    files.sort();
+    files
+}
+
+#[test]
+fn persistent_log_matches_memory_and_resumes_tip_hash() {
+    let dir = scratch_dir("event-store-resume");
+    let mut memory = Runtime::new("fingerprint".into()).expect("runtime");
+    let mut persistent = persistent_runtime(StoreConfig::new(&dir));
+    for seq in 0..5 {
+        memory.evaluate(&common::sample_input(seq));
+        persistent.evaluate(&common::sample_input(seq));
+    }
+    assert_eq!(persistent.tip_hash(), memory.tip_hash());
+    assert!(persistent.log.records.is_empty());
+    drop(persistent);
+
+    let log = EventLog::open(StoreConfig::new(&dir)).expect("reopen log");
+    assert_eq!(log.tip_hash, memory.tip_hash());
+    let mut resumed = Runtime::new("fingerprint".into())
+        .expect("runtime")
+        .with_log(log)
+        .expect("resume log");
+    assert_eq!(resumed.snapshot(), memory.snapshot());
+    resumed.evaluate(&common::sample_input(5));
+    let tip = resumed.tip_hash();
+    drop(resumed);
+
+    // Reopening verifies that the new record links to the resumed tip.
+    let log = EventLog::open(StoreConfig::new(&dir)).expect("reopen log");
+    assert_eq!(log.tip_hash, tip);
+    assert_ne!(tip, memory.tip_hash());
+    fs::remove_dir_all(&dir).expect("remove log dir");
+}
+
+#[test]
+fn torn_final_write_is_truncated_on_reopen() {
+    let dir = scratch_dir("event-store-torn");
+    let mut runtime = persistent_runtime(StoreConfig::new(&dir));
+    for seq in 0..3 {
+        runtime.evaluate(&common::sample_input(seq));
+    }
+    let tip = runtime.tip_hash();
+    drop(runtime);
+
+    let segment = segment_files(&dir).pop().expect("segment");
+    let intact_len = fs::metadata(&segment).expect("metadata").len();
+    let torn = br#"{"seq":3,"payload":{"Decision":{"verdict":"Al"#;
+    OpenOptions::new()
+        .append(true)
+        .open(&segment)
+        .expect("open segment")
+        .write_all(torn)
+        .expect("write torn record");
+
+    let log = EventLog::open(StoreConfig::new(&dir)).expect("reopen log");
+    let store = log.store().expect("persistent store");
+    assert_eq!(store.truncated_bytes(), torn.len() as u64);
+    assert_eq!(log.tip_hash, tip);
+    assert_eq!(fs::metadata(&segment).expect("metadata").len(), intact_len);
+
+    let mut runtime = Runtime::new("fingerprint".into())
+        .expect("runtime")
+        .with_log(log)
+        .expect("resume log");
+    runtime.evaluate(&common::sample_input(3));
+    let tip = runtime.tip_hash();
+    drop(runtime);
+    let log = EventLog::open(StoreConfig::new(&dir)).expect("reopen log");
+    assert_eq!(log.store().expect("store").truncated_bytes(), 0);
+    assert_eq!(log.tip_hash, tip);
+    fs::remove_dir_all(&dir).expect("remove log dir");
+}
+
+#[test]
+fn modified_record_is_reported_as_corruption() {
+    let dir = scratch_dir("event-store-corrupt");
+    let mut runtime = persistent_runtime(StoreConfig::new(&dir));
+    for seq in 0..3 {
+        runtime.evaluate(&common::sample_input(seq));
+    }
+    drop(runtime);
+
+    let segment = segment_files(&dir).pop().expect("segment");
+    let contents = fs::read_to_string(&segment).expect("read segment");
+    fs::write(&segment, contents.replacen("\"seq\":1", "\"seq\":7", 1)).expect("write segment");
+
+    let err = EventLog::open(StoreConfig::new(&dir)).expect_err("corruption detected");
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+    assert!(err.to_string().contains("hash does not match"));
+    fs::remove_dir_all(&dir).expect("remove log dir");
+}
+
+#[test]
+fn segments_rotate_and_chain_across_reopen() {
+    let dir = scratch_dir("event-store-rotate");
+    let config = StoreConfig {
+        segment_max_bytes: 1,
+        fsync: FsyncPolicy::Every(2),
+        ..StoreConfig::new(&dir)
+    };
+    let mut runtime = persistent_runtime(config.clone());
+    for seq in 0..4 {
+        runtime.evaluate(&common::sample_input(seq));
+    }
+    runtime.log.sync().expect("sync log");
+    let tip = runtime.tip_hash();
+    drop(runtime);
+
+    // Each record exceeds the limit, so each gets a segment of its own.
+    assert_eq!(segment_files(&dir).len(), 4);
+    let mut runtime = persistent_runtime(config);
+    runtime.evaluate(&common::sample_input(4));
+    drop(runtime);
+
+    // The resume point and the new evaluation each take a segment.
+    let segments = segment_files(&dir);
+    assert_eq!(segments.len(), 6);
+    let resumed = fs::read_to_string(&segments[4]).expect("read segment");
+    assert!(resumed.contains("Resumed"));
+    assert!(resumed.contains(&format!("\"prev_hash\":\"{tip}\"")));
+    fs::remove_dir_all(&dir).expect("remove log dir");
+}
+
+#[test]
+fn retried_rotation_reuses_an_empty_segment() {
+    let dir = scratch_dir("event-store-retry");
+    let config = StoreConfig {
+        segment_max_bytes: 1,
+        ..StoreConfig::new(&dir)
+    };
+    let mut runtime = persistent_runtime(config.clone());
+    runtime.evaluate(&common::sample_input(0));
+    // A rotation that created the next segment but failed to sync the
+    // directory leaves it empty behind.
+    fs::File::create(dir.join("segment-00000002.jsonl")).expect("create segment");
+    runtime.evaluate(&common::sample_input(1));
+    assert!(runtime.log.store_error().is_none());
+    assert!(runtime.log.records.is_empty());
+    let tip = runtime.tip_hash();
+    drop(runtime);
+
+    assert_eq!(segment_files(&dir).len(), 2);
+    let log = EventLog::open(config).expect("reopen log");
+    assert_eq!(log.tip_hash, tip);
+    fs::remove_dir_all(&dir).expect("remove log dir");
+}
+
+#[test]
+fn sealed_segments_are_verified() {
+    let dir = scratch_dir("event-store-sealed");
+    let config = StoreConfig {
+        segment_max_bytes: 1,
+        ..StoreConfig::new(&dir)
+    };
+    let mut runtime = persistent_runtime(config.clone());
+    for seq in 0..3 {
+        runtime.evaluate(&common::sample_input(seq));
+    }
+    drop(runtime);
+    let segments = segment_files(&dir);
+
+    // Only the last segment may end in a torn write.
+    let sealed = fs::read(&segments[0]).expect("read segment");
+    OpenOptions::new()
+        .append(true)
+        .open(&segments[0])
+        .expect("open segment")
+        .write_all(br#"{"seq":3"#)
+        .expect("write torn record");
+    let err = read_records(&dir).expect_err("torn sealed segment");
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+    assert!(err.to_string().contains("partial line"));
+    fs::write(&segments[0], sealed).expect("restore segment");
+    assert_eq!(read_records(&dir).expect("read log").len(), 3);
+
+    // The chain resumes from a verified record of the previous segment.
+    let contents = fs::read_to_string(&segments[1]).expect("read segment");
+    fs::write(&segments[1], contents.replacen("\"seq\":1", "\"seq\":7", 1)).expect("write segment");
+    let err = EventLog::open(config).expect_err("corruption detected");
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+    assert!(err.to_string().contains("hash does not match"));
+    fs::remove_dir_all(&dir).expect("remove log dir");
+}
+
+#[test]
+fn failing_store_holds_and_caps_pending_records() {
+    let dir = scratch_dir("event-store-failing");
+    let config = StoreConfig {
+        segment_max_bytes: 1,
+        fsync: FsyncPolicy::Never,
+        ..StoreConfig::new(&dir)
+    };
+    let mut runtime = persistent_runtime(config.clone());
+    let out = runtime.evaluate(&common::sample_input(0));
+    assert!(!out.reasons.contains(&ReasonCode::EventLogFault));
+
+    // Every further record needs a new segment, which cannot be created.
+    fs::remove_dir_all(&dir).expect("remove log dir");
+    runtime.evaluate(&common::sample_input(1));
+    assert!(runtime.log.faulted());
+    let out = runtime.evaluate(&common::sample_input(2));
+    assert_eq!(out.verdict, Verdict::Hold);
+    assert!(out.reasons.contains(&ReasonCode::EventLogFault));
+
+    let extra = 5;
+    for seq in 3..(MAX_PENDING_RECORDS + extra) as u64 {
+        runtime.evaluate(&common::sample_input(seq));
+    }
+    assert_eq!(runtime.log.records.len(), MAX_PENDING_RECORDS);
+    assert_eq!(runtime.log.dropped(), extra as u64 - 1);
+
+    // Once the store recovers, the drops are logged and the fault clears.
+    fs::create_dir_all(&dir).expect("restore log dir");
+    runtime.evaluate(&common::sample_input((MAX_PENDING_RECORDS + extra) as u64));
+    assert!(runtime.log.records.is_empty());
+    assert!(!runtime.log.faulted());
+    let records = read_records(&dir).expect("read log");
+    assert!(records.iter().any(|r| matches!(
+        r.payload,
+        EventPayload::Runtime(RuntimeEvent::RecordsDropped { count }) if count == extra as u64 - 1
+    )));
+    drop(runtime);
+    fs::remove_dir_all(&dir).expect("remove log dir");
+}
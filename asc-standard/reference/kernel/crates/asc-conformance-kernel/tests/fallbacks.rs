@@ -0,0 +1,77 @@
+mod common;
+
+use asc_kernel_model::Limits;
+use asc_kernel_runtime::Runtime;
+use asc_types::{model::KernelInput, Verdict};
+
+fn input(frame: &str, soc_percent: f64, input_age_ms: u64) -> KernelInput {
+    let mut input = common::nominal_input(1, 0);
+    input.state.frame = frame.try_into().expect("frame fits");
+    input.state.soc_percent = soc_percent;
+    input.state.input_age_ms = input_age_ms;
+    input.intent.desired_rates_dps = [5.0, 5.0, 5.0];
+    input.intent.desired_climb_mps = 2.0;
+    input
+}
+
+#[test]
+fn verdict_fallbacks_come_from_limits() {
+    let mut limits = Limits::baked();
+    // uas-small is fixed-wing, so Hold applies the loiter command.
+    limits.fallbacks.loiter.rates_dps = [0.0, 0.0, 4.0];
+    limits.fallbacks.loiter.climb_mps = 0.5;
+    limits.fallbacks.shutdown.climb_mps = -0.5;
+
+    let mut runtime = Runtime::with_limits("fingerprint".into(), limits.clone()).expect("runtime");
+    let out = runtime.evaluate(&input("NED", 10.0, 0));
+    assert_eq!(out.verdict, Verdict::Hold);
+    assert_eq!(out.command.applied_rates_dps, [0.0, 0.0, 4.0]);
+    assert_eq!(out.command.applied_climb_mps, 0.5);
+    assert!(!out.command.shutdown);
+
+    let mut runtime = Runtime::with_limits("fingerprint".into(), limits).expect("runtime");
+    let out = runtime.evaluate(&input("ENU", 90.0, 0));
+    assert_eq!(out.verdict, Verdict::Shutdown);
+    assert_eq!(out.command.applied_climb_mps, -0.5);
+    assert!(out.command.shutdown);
+}
+
+#[test]
+fn fallback_commands_are_projected_through_the_barrier() {
+    let mut limits = Limits::baked();
+    limits.fallbacks.hold.climb_mps = -2.0;
+    limits.fallbacks.loiter.climb_mps = -2.0;
+    let mut runtime = Runtime::with_limits("fingerprint".into(), limits.clone()).expect("runtime");
+    let mut tick = input("NED", 10.0, 0);
+    tick.state.position_m[2] = -(limits.invariants.min_altitude_m + 0.5);
+    let out = runtime.evaluate(&tick);
+    assert_eq!(out.verdict, Verdict::Hold);
+    assert_eq!(
+        out.command.applied_climb_mps,
+        -0.5 * limits.invariants.barrier_gain_per_s
+    );
+}
+
+#[test]
+fn fixed_wing_override_does_not_descend() {
+    let mut runtime =
+        Runtime::from_repo(&common::repo_root(), "fixed-wing").expect("runtime from repo");
+    let mut tick = input("NED", 90.0, 0);
+    tick.state.position_m = [5000.0, 0.0, -20.0];
+    let out = runtime.evaluate(&tick);
+    assert_eq!(out.verdict, Verdict::Override);
+    assert!(out.command.applied_climb_mps >= 0.0);
+    assert_eq!(
+        out.command.applied_rates_dps,
+        runtime.limits().fallbacks.r#override.rates_dps
+    );
+}
+
+#[test]
+fn vtol_override_descends_gently() {
+    let base = Runtime::from_repo(&common::repo_root(), "uas-small").expect("runtime from repo");
+    let vtol = Runtime::from_repo(&common::repo_root(), "hybrid-vtol").expect("runtime from repo");
+    let (base, vtol) = (&base.limits().fallbacks, &vtol.limits().fallbacks);
+    assert!(vtol.r#override.climb_mps < 0.0);
+    assert!(vtol.r#override.climb_mps > base.r#override.climb_mps);
+}
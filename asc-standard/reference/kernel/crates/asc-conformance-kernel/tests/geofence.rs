@@ -0,0 +1,66 @@
+mod common;
+
+use asc_kernel_runtime::Runtime;
+use asc_types::{model::KernelInput, ReasonCode, Verdict};
+
+fn repo_runtime() -> Runtime {
+    Runtime::from_repo(&common::repo_root(), "uas-small").expect("runtime from repo")
+}
+
+fn input_at(position_m: [f64; 3]) -> KernelInput {
+    let mut input = common::nominal_input(1, 0);
+    input.state.position_m = position_m;
+    input
+}
+
+#[test]
+fn repo_contract_loads_required_geofence() {
+    let runtime = repo_runtime();
+    let fence = runtime.limits().geofence.as_ref().expect("geofence loaded");
+    assert_eq!(fence.inclusion.id, "reference-range");
+    assert_eq!(fence.exclusions.len(), 1);
+}
+
+#[test]
+fn inside_inclusion_and_clear_of_exclusions_is_allowed() {
+    let out = repo_runtime().evaluate(&input_at([0.0, 0.0, -20.0]));
+    assert_eq!(out.verdict, Verdict::Allow);
+    assert!(out.reasons.is_empty());
+}
+
+#[test]
+fn leaving_inclusion_zone_is_geofence_violation() {
+    let out = repo_runtime().evaluate(&input_at([4500.0, 0.0, -20.0]));
+    assert_eq!(out.verdict, Verdict::Override);
+    assert_eq!(out.reasons, vec![ReasonCode::GeofenceViolation]);
+}
+
+#[test]
+fn entering_exclusion_zone_is_geofence_violation() {
+    let out = repo_runtime().evaluate(&input_at([1550.0, 1450.0, -20.0]));
+    assert_eq!(out.verdict, Verdict::Override);
+    assert_eq!(out.reasons, vec![ReasonCode::GeofenceViolation]);
+}
+
+#[test]
+fn overflying_exclusion_ceiling_is_allowed() {
+    let out = repo_runtime().evaluate(&input_at([1500.0, 1500.0, -80.0]));
+    assert_eq!(out.verdict, Verdict::Allow);
+}
+
+#[test]
+fn baked_limits_carry_the_profile_geofence() {
+    let mut runtime = Runtime::new("fingerprint".into()).expect("runtime");
+    assert_eq!(runtime.limits().geofence, repo_runtime().limits().geofence);
+    let out = runtime.evaluate(&input_at([4500.0, 0.0, -20.0]));
+    assert_eq!(out.reasons, vec![ReasonCode::GeofenceViolation]);
+}
+
+#[test]
+fn ceiling_is_an_altitude() {
+    // The ceiling is 100 m up, i.e. a down component of -100 m.
+    let out = repo_runtime().evaluate(&input_at([0.0, 0.0, -120.0]));
+    assert_eq!(out.reasons, vec![ReasonCode::GeofenceViolation]);
+    let out = repo_runtime().evaluate(&input_at([0.0, 0.0, -60.0]));
+    assert_eq!(out.verdict, Verdict::Allow);
+}
@@ -0,0 +1,147 @@
+mod common;
+
+use asc_logging::{
+    to_canonical_json, verify_chain, EventPayload, EventRecord, RuntimeEvent, SCHEMA_VERSION,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Deserialize)]
+struct Fixture {
+    fixture_id: String,
+    cases: Vec<Case>,
+}
+
+#[derive(Deserialize)]
+struct Case {
+    schema_version: u32,
+    tip_hash: String,
+    records: Vec<EventRecord>,
+}
+
+#[test]
+fn golden_chains_keep_their_hashes() {
+    let raw = std::fs::read_to_string(
+        common::repo_root().join("conformance/fixtures/hash-chain-golden.json"),
+    )
+    .expect("read golden fixture");
+    let fixture: Fixture = serde_json::from_str(&raw).expect("parse golden fixture");
+    let versions: Vec<_> = fixture.cases.iter().map(|c| c.schema_version).collect();
+    assert_eq!(versions, (1..=SCHEMA_VERSION).collect::<Vec<_>>());
+
+    for case in &fixture.cases {
+        let id = format!("{} v{}", fixture.fixture_id, case.schema_version);
+        assert!(case
+            .records
+            .iter()
+            .all(|r| r.schema_version == case.schema_version));
+        // Version 1 records carry no input; later ones log a NaN power reading.
+        if case.schema_version > 1 {
+            assert!(case.records.iter().any(|r| matches!(
+                &r.payload,
+                EventPayload::Evaluation(e) if e.input.state.power_w.is_nan()
+            )));
+        }
+        let report = verify_chain(&case.records, Some(&case.tip_hash));
+        assert!(report.is_intact(), "{id}: {report:?}");
+    }
+}
+
+#[test]
+fn record_preimage_is_canonical_json() {
+    let payload = EventPayload::Runtime(RuntimeEvent::ShutdownLatchReset {
+        authority: "ground-station".into(),
+    });
+    // SHA-256 of
+    // {"payload":{"Runtime":{"ShutdownLatchReset":{"authority":"ground-station"}}},"prev_hash":"","schema_version":3,"seq":7}
+    assert_eq!(
+        EventRecord::digest(3, 7, &payload, ""),
+        "e02a6439142d6a1512eb0899d889d92a4c9a24a0f3ce460e07a199eccadec609"
+    );
+}
+
+#[test]
+fn field_order_does_not_change_the_encoding() {
+    #[derive(Serialize)]
+    struct Before {
+        seq: u64,
+        rate_dps: f64,
+        phase: &'static str,
+    }
+    #[derive(Serialize)]
+    struct After {
+        phase: &'static str,
+        rate_dps: f64,
+        seq: u64,
+    }
+    let before = Before {
+        seq: 4,
+        rate_dps: 0.1,
+        phase: "Cruise",
+    };
+    let after = After {
+        phase: "Cruise",
+        rate_dps: 0.1,
+        seq: 4,
+    };
+    let expected = r#"{"phase":"Cruise","rate_dps":0.1,"seq":4}"#;
+    assert_eq!(to_canonical_json(&before).expect("encode"), expected);
+    assert_eq!(to_canonical_json(&after).expect("encode"), expected);
+}
+
+// Number and string vectors from RFC 8785 and its ECMAScript number format.
+#[test]
+fn canonical_encoding_matches_rfc_8785() {
+    for (value, expected) in [
+        (0.0, "0"),
+        (-0.0, "0"),
+        (1.0, "1"),
+        (-1.5, "-1.5"),
+        (0.1, "0.1"),
+        (4.5, "4.5"),
+        (0.002, "0.002"),
+        (0.000001, "0.000001"),
+        (1e-7, "1e-7"),
+        (1e21, "1e+21"),
+        (1e20, "100000000000000000000"),
+        (333333333.3333333, "333333333.3333333"),
+        (123456789012345680000.0, "123456789012345680000"),
+        (1.0 / 3.0, "0.3333333333333333"),
+        (9007199254740994.0, "9007199254740994"),
+        (5e-324, "5e-324"),
+        (-5e-324, "-5e-324"),
+        (1.7976931348623157e308, "1.7976931348623157e+308"),
+        (2.5e-10, "2.5e-10"),
+    ] {
+        assert_eq!(to_canonical_json(&value).expect("encode"), expected);
+    }
+    assert_eq!(
+        to_canonical_json(&u64::MAX).expect("encode"),
+        "18446744073709551615"
+    );
+    assert_eq!(to_canonical_json(&-7i64).expect("encode"), "-7");
+
+    let members: BTreeMap<&str, u8> = [
+        ("\u{20ac}", 1),
+        ("\r", 2),
+        ("\u{fb33}", 3),
+        ("1", 4),
+        ("\u{80}", 5),
+        ("\u{1f600}", 6),
+        ("\u{f6}", 7),
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(
+        to_canonical_json(&members).expect("encode"),
+        "{\"\\r\":2,\"1\":4,\"\u{80}\":5,\"\u{f6}\":7,\"\u{20ac}\":1,\"\u{1f600}\":6,\"\u{fb33}\":3}"
+    );
+    assert_eq!(
+        to_canonical_json("\u{20ac}$\u{f}\nA'B\"\\\\\"/\u{7f}").expect("encode"),
+        "\"\u{20ac}$\\u000f\\nA'B\\\"\\\\\\\\\\\"/\u{7f}\""
+    );
+    assert_eq!(
+        to_canonical_json(&(None::<f64>, true, [1.5f64; 2])).expect("encode"),
+        "[null,true,[1.5,1.5]]"
+    );
+}
@@ -0,0 +1,152 @@
+mod common;
+
+use asc_kernel_model::Limits;
+use asc_kernel_runtime::Runtime;
+use asc_types::{model::KernelInput, ReasonCode, Verdict};
+
+fn tick(seq: u64, soc_percent: f64, velocity_mps: f64) -> KernelInput {
+    let mut input = common::nominal_input(seq, seq * 10);
+    input.state.soc_percent = soc_percent;
+    input.state.velocity_mps = velocity_mps;
+    input
+}
+
+#[test]
+fn hold_is_sustained_until_clean_ticks_and_dwell_elapse() {
+    let limits = Limits::baked();
+    let mut runtime = Runtime::new("fingerprint".into()).expect("runtime");
+    let out = runtime.evaluate(&tick(1, 10.0, 0.0));
+    assert_eq!(out.verdict, Verdict::Hold);
+
+    // Ticks are 10 ms apart, so the 100 ms dwell outlasts the 5 clean ticks.
+    let release_seq = 1 + limits.deescalation.min_dwell_ms / 10;
+    for seq in 2..release_seq {
+        let out = runtime.evaluate(&tick(seq, 90.0, 0.0));
+        assert_eq!(out.verdict, Verdict::Hold, "seq {seq}");
+        assert_eq!(out.reasons, vec![ReasonCode::DeescalationPending]);
+    }
+    let out = runtime.evaluate(&tick(release_seq, 90.0, 0.0));
+    assert_eq!(out.verdict, Verdict::Allow);
+    assert!(out.reasons.is_empty());
+    assert!(runtime.kernel_state().held.is_none());
+}
+
+#[test]
+fn clean_tick_count_gates_release_when_dwell_is_zero() {
+    let mut limits = Limits::baked();
+    limits.deescalation.min_dwell_ms = 0;
+    limits.deescalation.clean_ticks = 3;
+    let mut runtime = Runtime::with_limits("fingerprint".into(), limits).expect("runtime");
+    let _ = runtime.evaluate(&tick(1, 10.0, 0.0));
+    assert_eq!(runtime.evaluate(&tick(2, 90.0, 0.0)).verdict, Verdict::Hold);
+    assert_eq!(runtime.evaluate(&tick(3, 90.0, 0.0)).verdict, Verdict::Hold);
+    assert_eq!(
+        runtime.evaluate(&tick(4, 90.0, 0.0)).verdict,
+        Verdict::Allow
+    );
+}
+
+#[test]
+fn offending_tick_restarts_the_count() {
+    let mut limits = Limits::baked();
+    limits.deescalation.min_dwell_ms = 0;
+    limits.deescalation.clean_ticks = 2;
+    let mut runtime = Runtime::with_limits("fingerprint".into(), limits).expect("runtime");
+    let _ = runtime.evaluate(&tick(1, 10.0, 0.0));
+    let _ = runtime.evaluate(&tick(2, 90.0, 0.0));
+    let out = runtime.evaluate(&tick(3, 10.0, 0.0));
+    assert_eq!(out.reasons, vec![ReasonCode::EnergyBudgetExceeded]);
+    assert_eq!(runtime.evaluate(&tick(4, 90.0, 0.0)).verdict, Verdict::Hold);
+    assert_eq!(
+        runtime.evaluate(&tick(5, 90.0, 0.0)).verdict,
+        Verdict::Allow
+    );
+}
+
+#[test]
+fn lower_offending_ticks_do_not_release_a_held_override() {
+    let mut limits = Limits::baked();
+    limits.deescalation.min_dwell_ms = 0;
+    limits.deescalation.clean_ticks = 2;
+    let late_ms = limits.guarantees.max_tick_interval_ms + 10;
+    let mut runtime = Runtime::with_limits("fingerprint".into(), limits).expect("runtime");
+    let _ = runtime.evaluate(&tick(1, 90.0, 0.0));
+    let mut late = tick(2, 90.0, 0.0);
+    late.tick.ts_ms = 10 + late_ms;
+    assert_eq!(runtime.evaluate(&late).verdict, Verdict::Override);
+
+    // Ticks that trip a Hold check stay below the held Override but are not
+    // clean, so they neither release it nor count towards its release.
+    for seq in 3..6 {
+        let mut low = tick(seq, 10.0, 0.0);
+        low.tick.ts_ms = late.tick.ts_ms + (seq - 2) * 10;
+        let out = runtime.evaluate(&low);
+        assert_eq!(out.verdict, Verdict::Override, "seq {seq}");
+        assert!(out.reasons.contains(&ReasonCode::DeescalationPending));
+    }
+    for (seq, verdict) in [(6, Verdict::Override), (7, Verdict::Allow)] {
+        let mut clean = tick(seq, 90.0, 0.0);
+        clean.tick.ts_ms = late.tick.ts_ms + (seq - 2) * 10;
+        assert_eq!(runtime.evaluate(&clean).verdict, verdict, "seq {seq}");
+    }
+}
+
+#[test]
+fn zero_rules_disable_hysteresis() {
+    let mut limits = Limits::baked();
+    limits.deescalation.min_dwell_ms = 0;
+    limits.deescalation.clean_ticks = 0;
+    let mut runtime = Runtime::with_limits("fingerprint".into(), limits).expect("runtime");
+    assert_eq!(runtime.evaluate(&tick(1, 10.0, 0.0)).verdict, Verdict::Hold);
+    assert_eq!(
+        runtime.evaluate(&tick(2, 90.0, 0.0)).verdict,
+        Verdict::Allow
+    );
+}
+
+#[test]
+fn soc_floor_releases_only_above_exit_band() {
+    let mut limits = Limits::baked();
+    limits.deescalation.min_dwell_ms = 0;
+    limits.deescalation.clean_ticks = 0;
+    let floor = limits.energy.min_soc_percent;
+    let band = limits.deescalation.soc_exit_band_percent;
+    let mut runtime = Runtime::with_limits("fingerprint".into(), limits).expect("runtime");
+
+    let _ = runtime.evaluate(&tick(1, floor - 0.5, 0.0));
+    let out = runtime.evaluate(&tick(2, floor + band / 2.0, 0.0));
+    assert_eq!(out.verdict, Verdict::Hold);
+    assert_eq!(out.reasons, vec![ReasonCode::EnergyBudgetExceeded]);
+
+    let out = runtime.evaluate(&tick(3, floor + band, 0.0));
+    assert_eq!(out.verdict, Verdict::Allow);
+}
+
+#[test]
+fn overspeed_releases_only_below_exit_band() {
+    let limits = Limits::baked();
+    let max = limits.state.max_speed_mps;
+    let band = limits.deescalation.speed_exit_band_mps;
+    let mut runtime = Runtime::new("fingerprint".into()).expect("runtime");
+
+    let _ = runtime.evaluate(&tick(1, 90.0, max + 1.0));
+    let out = runtime.evaluate(&tick(2, 90.0, max - band / 2.0));
+    assert_eq!(out.verdict, Verdict::Clamp);
+    assert_eq!(out.reasons, vec![ReasonCode::StateOutOfBounds]);
+
+    let out = runtime.evaluate(&tick(3, 90.0, max - band));
+    assert_eq!(out.verdict, Verdict::Allow);
+}
+
+#[test]
+fn hysteresis_replay_is_deterministic() {
+    let socs = [90.0, 10.0, 90.0, 90.0, 10.0, 90.0, 90.0, 90.0, 90.0, 90.0];
+    let run = || {
+        let mut runtime = Runtime::new("fingerprint".into()).expect("runtime");
+        for (i, soc) in socs.iter().enumerate() {
+            let _ = runtime.evaluate(&tick(i as u64 + 1, *soc, 0.0));
+        }
+        runtime.tip_hash()
+    };
+    assert_eq!(run(), run());
+}
@@ -0,0 +1,123 @@
+mod common;
+
+use asc_kernel_runtime::Runtime;
+use asc_logging::{EventPayload, RuntimeEvent};
+use asc_types::{
+    model::{CheckId, KernelInput},
+    ReasonCode, Severity, Verdict,
+};
+
+fn commanding_input(seq: u64, ts_ms: u64) -> KernelInput {
+    let mut input = common::nominal_input(seq, ts_ms);
+    input.intent.desired_rates_dps = [5.0, 0.0, 0.0];
+    input.intent.desired_climb_mps = 1.0;
+    input
+}
+
+#[test]
+fn disarmed_vehicle_refuses_commands() {
+    let mut runtime = Runtime::new("fingerprint".into()).expect("runtime");
+    let mut input = commanding_input(1, 0);
+    input.state.armed = false;
+
+    let out = runtime.evaluate(&input);
+    assert_eq!(out.verdict, Verdict::Hold);
+    assert_eq!(out.reasons, vec![ReasonCode::InterlockDisarmed]);
+    let interlock = out
+        .diagnostics
+        .iter()
+        .find(|d| d.check == CheckId::Interlock)
+        .expect("interlock outcome");
+    assert_eq!(interlock.severity, Severity::Critical);
+    // uas-small is fixed-wing, so its Hold fallback loiters.
+    let loiter = runtime.limits().fallbacks.loiter;
+    assert_eq!(out.command.applied_rates_dps, loiter.rates_dps);
+    assert_eq!(out.command.applied_climb_mps, loiter.climb_mps);
+}
+
+#[test]
+fn missing_armed_deserializes_as_disarmed() {
+    let mut value = serde_json::to_value(commanding_input(1, 0)).expect("serialize input");
+    value["state"]
+        .as_object_mut()
+        .expect("state object")
+        .remove("armed");
+    let input: KernelInput = serde_json::from_value(value).expect("input without armed");
+    assert!(!input.state.armed);
+
+    let mut runtime = Runtime::new("fingerprint".into()).expect("runtime");
+    let out = runtime.evaluate(&input);
+    assert_eq!(out.reasons, vec![ReasonCode::InterlockDisarmed]);
+}
+
+#[test]
+fn disarmed_vehicle_still_passes_shutdown() {
+    let mut runtime = Runtime::new("fingerprint".into()).expect("runtime");
+    let mut input = commanding_input(1, 0);
+    input.state.armed = false;
+    input.state.frame = "ENU".try_into().expect("frame fits");
+
+    let out = runtime.evaluate(&input);
+    assert_eq!(out.verdict, Verdict::Shutdown);
+    assert!(out.command.shutdown);
+}
+
+#[test]
+fn shutdown_latches_across_recovered_ticks() {
+    let mut runtime = Runtime::new("fingerprint".into()).expect("runtime");
+    let mut input = commanding_input(1, 0);
+    input.state.bank_deg = 75.0;
+    assert_eq!(runtime.evaluate(&input).verdict, Verdict::Shutdown);
+    assert!(runtime.shutdown_latched());
+
+    let out = runtime.evaluate(&commanding_input(2, 10));
+    assert_eq!(out.verdict, Verdict::Shutdown);
+    assert_eq!(out.reasons, vec![ReasonCode::ShutdownLatched]);
+    assert!(out.command.shutdown);
+}
+
+#[test]
+fn explicit_reset_clears_latch_and_is_logged() {
+    let mut runtime = Runtime::new("fingerprint".into()).expect("runtime");
+    let mut input = commanding_input(1, 0);
+    input.state.bank_deg = 75.0;
+    let _ = runtime.evaluate(&input);
+    let tip_before = runtime.tip_hash();
+
+    assert!(runtime
+        .reset_shutdown_latch(1, "ground-station")
+        .expect("valid reset"));
+    assert!(!runtime
+        .reset_shutdown_latch(1, "ground-station")
+        .expect("valid reset"));
+    assert_ne!(runtime.tip_hash(), tip_before);
+    let last = runtime.log.records.last().expect("reset record");
+    assert!(matches!(
+        &last.payload,
+        EventPayload::Runtime(RuntimeEvent::ShutdownLatchReset { authority })
+            if authority == "ground-station"
+    ));
+
+    let out = runtime.evaluate(&commanding_input(2, 10));
+    assert_eq!(out.verdict, Verdict::Allow);
+}
+
+#[test]
+fn reset_requires_the_current_seq_and_a_declared_authority() {
+    let mut runtime = Runtime::new("fingerprint".into()).expect("runtime");
+    let mut input = commanding_input(1, 0);
+    input.state.bank_deg = 75.0;
+    let _ = runtime.evaluate(&input);
+    let tip = runtime.tip_hash();
+
+    let err = runtime
+        .reset_shutdown_latch(2, "ground-station")
+        .expect_err("future seq rejected");
+    assert!(err.to_string().contains("does not follow"));
+    let err = runtime
+        .reset_shutdown_latch(1, "bystander")
+        .expect_err("undeclared authority rejected");
+    assert!(err.to_string().contains("not a declared reset authority"));
+    assert!(runtime.shutdown_latched());
+    assert_eq!(runtime.tip_hash(), tip);
+}
@@ -0,0 +1,132 @@
+mod common;
+
+use asc_kernel_model::{
+    constrain, Check, CheckParams, CheckRegistry, KernelState, Limits, Reading, TickContext,
+};
+use asc_kernel_runtime::Runtime;
+use asc_types::{
+    model::{FlightPhase, KernelInput, TickAnomaly},
+    ReasonCode, Severity, Verdict,
+};
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+// Counts heap allocations made by the current thread so that tests running in
+// parallel do not disturb each other.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|n| n.set(n.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.with(|n| n.set(n.get() + 1));
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocations() -> usize {
+    ALLOCATIONS.with(Cell::get)
+}
+
+fn repo_limits(profile: &str) -> Limits {
+    Runtime::from_repo(&common::repo_root(), profile)
+        .expect("runtime from repo")
+        .limits()
+        .clone()
+}
+
+struct PowerMargin;
+
+impl Check for PowerMargin {
+    fn name(&self) -> &str {
+        "power-margin"
+    }
+    fn version(&self) -> &str {
+        "1"
+    }
+    fn verdict(&self) -> Verdict {
+        Verdict::Clamp
+    }
+    fn reason(&self) -> ReasonCode {
+        ReasonCode::PowerLimitExceeded
+    }
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn evaluate(&self, input: &KernelInput, limits: &Limits, _params: &CheckParams) -> Reading {
+        Reading::Upper {
+            measured: input.state.power_w,
+            limit: limits.energy.max_power_w,
+        }
+    }
+}
+
+fn input(velocity_mps: f64, bank_deg: f64, yaw_rate_dps: f64) -> KernelInput {
+    let mut input = common::nominal_input(1, 0);
+    input.state.velocity_mps = velocity_mps;
+    input.state.bank_deg = bank_deg;
+    input.intent.desired_rates_dps = [0.0, 0.0, yaw_rate_dps];
+    input
+}
+
+#[test]
+fn constrain_performs_no_heap_allocation() {
+    let anomalies = [
+        TickAnomaly::SkippedSeq {
+            expected_seq: 2,
+            seq: 4,
+        },
+        TickAnomaly::ClockRegression {
+            ts_ms: 0,
+            last_ts_ms: 10,
+        },
+    ];
+    let ctx = TickContext {
+        inter_tick_ms: Some(10),
+        anomalies: heapless::Vec::from_slice(&anomalies).expect("anomalies fit"),
+        temporal_violations: 1,
+        phase: Some(FlightPhase::Landing),
+        phase_validated: true,
+        ..TickContext::default()
+    };
+    for limits in [Limits::baked(), repo_limits("hybrid-vtol")] {
+        let mut state = KernelState::default();
+        let ticks = [
+            (input(0.0, 0.0, 0.0), TickContext::default()),
+            (input(11.0, 0.0, 200.0), TickContext::default()),
+            (input(20.0, 70.0, 0.0), ctx.clone()),
+        ];
+        let mut verdicts = Vec::with_capacity(ticks.len());
+
+        let params = CheckParams::new();
+        let before = allocations();
+        let mut registry = CheckRegistry::new();
+        assert!(registry.register(&PowerMargin, &params).is_ok());
+        for (input, ctx) in &ticks {
+            let out = constrain(input, &limits, &registry, ctx, &mut state);
+            verdicts.push(out.verdict);
+        }
+        assert_eq!(allocations(), before);
+
+        assert_eq!(
+            verdicts,
+            vec![Verdict::Allow, Verdict::Clamp, Verdict::Shutdown]
+        );
+    }
+}
@@ -0,0 +1,73 @@
+mod common;
+
+use asc_kernel_runtime::Runtime;
+use asc_types::{model::KernelInput, ReasonCode, Verdict};
+
+fn moving_input() -> KernelInput {
+    let mut input = common::nominal_input(1, 0);
+    input.state.velocity_mps = 10.0;
+    input.intent.desired_rates_dps = [5.0, 5.0, 5.0];
+    input.intent.desired_climb_mps = 1.0;
+    input
+}
+
+type Corruption = (&'static str, fn(&mut KernelInput, f64));
+
+fn corruptions() -> Vec<Corruption> {
+    vec![
+        ("position_m[0]", |i, v| i.state.position_m[0] = v),
+        ("position_m[1]", |i, v| i.state.position_m[1] = v),
+        ("position_m[2]", |i, v| i.state.position_m[2] = v),
+        ("velocity_mps", |i, v| i.state.velocity_mps = v),
+        ("airspeed_mps", |i, v| i.state.airspeed_mps = Some(v)),
+        ("bank_deg", |i, v| i.state.bank_deg = v),
+        ("pitch_deg", |i, v| i.state.pitch_deg = v),
+        ("soc_percent", |i, v| i.state.soc_percent = v),
+        ("power_w", |i, v| i.state.power_w = v),
+        ("battery_capacity_wh", |i, v| {
+            i.state.battery_capacity_wh = v
+        }),
+        ("desired_rates_dps[0]", |i, v| {
+            i.intent.desired_rates_dps[0] = v
+        }),
+        ("desired_rates_dps[1]", |i, v| {
+            i.intent.desired_rates_dps[1] = v
+        }),
+        ("desired_rates_dps[2]", |i, v| {
+            i.intent.desired_rates_dps[2] = v
+        }),
+        ("desired_climb_mps", |i, v| i.intent.desired_climb_mps = v),
+    ]
+}
+
+#[test]
+fn every_non_finite_field_is_rejected_conservatively() {
+    for (field, corrupt) in corruptions() {
+        for value in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            let mut runtime = Runtime::new("fingerprint".into()).expect("runtime");
+            let mut input = moving_input();
+            corrupt(&mut input, value);
+
+            let out = runtime.evaluate(&input);
+            assert!(
+                out.reasons.contains(&ReasonCode::NonFiniteInput),
+                "{field}={value} not flagged"
+            );
+            assert!(
+                out.verdict.precedence() >= Verdict::Hold.precedence(),
+                "{field}={value} yielded {:?}",
+                out.verdict
+            );
+            assert!(out.command.applied_rates_dps.iter().all(|v| v.is_finite()));
+            assert!(out.command.applied_climb_mps.is_finite());
+        }
+    }
+}
+
+#[test]
+fn finite_input_is_not_flagged() {
+    let mut runtime = Runtime::new("fingerprint".into()).expect("runtime");
+    let out = runtime.evaluate(&moving_input());
+    assert_eq!(out.verdict, Verdict::Allow);
+    assert!(!out.reasons.contains(&ReasonCode::NonFiniteInput));
+}
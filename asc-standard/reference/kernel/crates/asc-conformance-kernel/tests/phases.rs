@@ -0,0 +1,144 @@
+mod common;
+
+use asc_kernel_model::Limits;
+use asc_kernel_runtime::Runtime;
+use asc_logging::{EventPayload, RuntimeEvent};
+use asc_types::{
+    model::{FlightPhase, KernelInput},
+    ReasonCode, Verdict,
+};
+
+fn tick(seq: u64, phase: FlightPhase, altitude_m: f64, climb_mps: f64) -> KernelInput {
+    let mut input = common::nominal_input(seq, seq * 10);
+    input.state.position_m = [0.0, 0.0, -altitude_m];
+    input.state.phase = phase;
+    input.intent.desired_climb_mps = climb_mps;
+    input
+}
+
+fn logged_phase_events(runtime: &Runtime) -> Vec<RuntimeEvent> {
+    runtime
+        .log
+        .records
+        .iter()
+        .filter_map(|r| match &r.payload {
+            EventPayload::Runtime(
+                e @ (RuntimeEvent::PhaseTransition { .. }
+                | RuntimeEvent::PhaseTransitionRejected { .. }),
+            ) => Some(e.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn takeoff_from_the_ground_is_not_shut_down() {
+    let mut runtime = Runtime::new("fingerprint".into()).expect("runtime");
+    let out = runtime.evaluate(&tick(1, FlightPhase::Ground, 0.0, 0.0));
+    assert_eq!(out.verdict, Verdict::Allow);
+
+    let out = runtime.evaluate(&tick(2, FlightPhase::Takeoff, 0.0, 1.0));
+    assert_eq!(out.verdict, Verdict::Allow);
+    assert_eq!(runtime.phase(), Some(FlightPhase::Takeoff));
+    assert_eq!(
+        logged_phase_events(&runtime),
+        vec![RuntimeEvent::PhaseTransition {
+            from: FlightPhase::Ground,
+            to: FlightPhase::Takeoff,
+        }]
+    );
+}
+
+#[test]
+fn illegal_transition_is_rejected_and_holds() {
+    let mut runtime = Runtime::new("fingerprint".into()).expect("runtime");
+    let _ = runtime.evaluate(&tick(1, FlightPhase::Ground, 0.0, 0.0));
+    let out = runtime.evaluate(&tick(2, FlightPhase::Cruise, 0.0, 0.0));
+    assert_eq!(out.verdict, Verdict::Hold);
+    assert_eq!(out.reasons, vec![ReasonCode::PhaseTransitionInvalid]);
+    assert_eq!(runtime.phase(), Some(FlightPhase::Ground));
+    assert_eq!(
+        logged_phase_events(&runtime),
+        vec![RuntimeEvent::PhaseTransitionRejected {
+            from: FlightPhase::Ground,
+            to: FlightPhase::Cruise,
+        }]
+    );
+}
+
+#[test]
+fn first_reported_phase_is_adopted() {
+    let mut runtime = Runtime::new("fingerprint".into()).expect("runtime");
+    let out = runtime.evaluate(&tick(1, FlightPhase::Landing, 20.0, 0.0));
+    assert_eq!(out.verdict, Verdict::Allow);
+    assert_eq!(runtime.phase(), Some(FlightPhase::Landing));
+    assert!(logged_phase_events(&runtime).is_empty());
+}
+
+#[test]
+fn phase_overrides_replace_flow_limits() {
+    let mut runtime = Runtime::new("fingerprint".into()).expect("runtime");
+    let landing = runtime
+        .limits()
+        .phases
+        .overrides
+        .iter()
+        .find(|o| o.phase == FlightPhase::Landing)
+        .and_then(|o| o.max_climb_rate_mps)
+        .expect("landing climb override");
+    let cruise = runtime.limits().flow.max_climb_rate_mps;
+    assert!(landing < cruise);
+
+    let out = runtime.evaluate(&tick(1, FlightPhase::Landing, 20.0, cruise));
+    assert_eq!(out.verdict, Verdict::Clamp);
+    assert_eq!(out.reasons, vec![ReasonCode::FlowConstraintViolation]);
+    assert_eq!(out.command.applied_climb_mps, landing);
+
+    let mut runtime = Runtime::new("fingerprint".into()).expect("runtime");
+    let out = runtime.evaluate(&tick(1, FlightPhase::Cruise, 20.0, cruise));
+    assert_eq!(out.verdict, Verdict::Allow);
+}
+
+#[test]
+fn implausible_phase_keeps_the_altitude_floor() {
+    // Reported as the first phase, Landing at 6 m above a 5 m floor is not yet
+    // plausible, so the barrier still holds the vehicle above the floor.
+    let mut runtime = Runtime::new("fingerprint".into()).expect("runtime");
+    let out = runtime.evaluate(&tick(1, FlightPhase::Landing, 6.0, -3.0));
+    assert_eq!(out.reasons, vec![ReasonCode::BarrierProjection]);
+    assert_eq!(out.command.applied_climb_mps, -1.0);
+
+    // Reached through Approach, the same Landing lowers the floor.
+    let mut runtime = Runtime::new("fingerprint".into()).expect("runtime");
+    let _ = runtime.evaluate(&tick(1, FlightPhase::Cruise, 20.0, -3.0));
+    let _ = runtime.evaluate(&tick(2, FlightPhase::Approach, 10.0, -3.0));
+    let _ = runtime.evaluate(&tick(3, FlightPhase::Landing, 8.0, -3.0));
+    let out = runtime.evaluate(&tick(4, FlightPhase::Landing, 6.0, -3.0));
+    assert_eq!(out.verdict, Verdict::Allow);
+    assert_eq!(out.command.applied_climb_mps, -3.0);
+
+    // Ground is only entered on the floor.
+    let out = runtime.evaluate(&tick(5, FlightPhase::Ground, 6.0, 0.0));
+    assert_eq!(out.reasons, vec![ReasonCode::PhaseTransitionInvalid]);
+    assert_eq!(runtime.phase(), Some(FlightPhase::Landing));
+    let out = runtime.evaluate(&tick(6, FlightPhase::Ground, 0.0, 0.0));
+    assert!(!out.reasons.contains(&ReasonCode::PhaseTransitionInvalid));
+    assert_eq!(runtime.phase(), Some(FlightPhase::Ground));
+}
+
+#[test]
+fn phase_overrides_never_loosen_profile_limits() {
+    let mut limits = Limits::baked();
+    limits.flow.max_climb_rate_mps = 2.0;
+    limits.invariants.max_bank_deg = 15.0;
+    let landing = limits.envelope(Some(FlightPhase::Landing), true, Some(0.0));
+    assert_eq!(landing.flow.max_climb_rate_mps, 2.0);
+    assert_eq!(landing.invariants.max_bank_deg, 15.0);
+    assert_eq!(landing.invariants.min_altitude_m, 0.0);
+
+    let unvalidated = limits.envelope(Some(FlightPhase::Landing), false, Some(0.0));
+    assert_eq!(
+        unvalidated.invariants.min_altitude_m,
+        limits.invariants.min_altitude_m
+    );
+}
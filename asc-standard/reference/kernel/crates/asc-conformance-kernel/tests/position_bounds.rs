@@ -0,0 +1,73 @@
+mod common;
+
+use asc_kernel_model::Limits;
+use asc_kernel_runtime::Runtime;
+use asc_types::{ReasonCode, ReasonSet, Verdict};
+
+// The baked geofence lies inside the position bounds, so it is dropped to
+// reach their edges.
+fn unfenced() -> Limits {
+    let mut limits = Limits::baked();
+    limits.geofence = None;
+    limits
+}
+
+fn evaluate_at(position_m: [f64; 3]) -> (Verdict, ReasonSet) {
+    let mut runtime = Runtime::with_limits("fingerprint".into(), unfenced()).expect("runtime");
+    let mut input = common::nominal_input(1, 0);
+    input.state.position_m = position_m;
+    let out = runtime.evaluate(&input);
+    (out.verdict, out.reasons)
+}
+
+#[test]
+fn position_inside_box_is_allowed() {
+    let (verdict, reasons) = evaluate_at([0.0, 0.0, -20.0]);
+    assert_eq!(verdict, Verdict::Allow);
+    assert!(reasons.is_empty());
+}
+
+#[test]
+fn position_on_box_edge_is_allowed() {
+    let limits = Limits::baked();
+    let (verdict, _) = evaluate_at([
+        limits.state.position_max_m[0],
+        limits.state.position_min_m[1],
+        limits.state.position_min_m[2],
+    ]);
+    assert_eq!(verdict, Verdict::Allow);
+}
+
+#[test]
+fn each_axis_and_bound_trips_state_out_of_bounds() {
+    let limits = Limits::baked();
+    for axis in 0..3 {
+        for (bound, delta) in [
+            (limits.state.position_min_m[axis], -1.0),
+            (limits.state.position_max_m[axis], 1.0),
+        ] {
+            let mut position_m = [0.0, 0.0, -20.0];
+            position_m[axis] = bound + delta;
+            let (verdict, reasons) = evaluate_at(position_m);
+            assert!(
+                reasons.contains(&ReasonCode::StateOutOfBounds),
+                "axis {axis} at {} not flagged",
+                position_m[axis]
+            );
+            assert!(verdict.precedence() >= limits.state.position_bounds_verdict.precedence());
+        }
+    }
+}
+
+#[test]
+fn position_bounds_verdict_is_configurable() {
+    let mut limits = unfenced();
+    limits.state.position_bounds_verdict = Verdict::Override;
+    let mut runtime = Runtime::with_limits("fingerprint".into(), limits).expect("runtime");
+    let mut input = common::nominal_input(1, 0);
+    input.state.position_m[0] = 6000.0;
+
+    let out = runtime.evaluate(&input);
+    assert_eq!(out.verdict, Verdict::Override);
+    assert_eq!(out.reasons, vec![ReasonCode::StateOutOfBounds]);
+}
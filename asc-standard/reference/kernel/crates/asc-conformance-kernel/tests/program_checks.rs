@@ -0,0 +1,231 @@
+mod common;
+
+use asc_kernel_model::{Check, CheckParams, Limits, Reading};
+use asc_kernel_runtime::Runtime;
+use asc_types::{
+    model::{CheckId, ExtensionSignal, KernelInput},
+    ReasonCode, Severity, Verdict,
+};
+use std::{fs, path::PathBuf};
+
+const DECLARED: &str = "checks:
+  - id: power-margin
+    reason: PowerLimitExceeded
+    severity: Warning
+    verdict: Clamp
+    params:
+      max_power_w: 350
+  - id: link-loss
+    reason: InputStale
+    severity: Critical
+    verdict: Hold
+    params:
+      min_link_quality: 0.5
+";
+
+struct PowerMargin {
+    version: &'static str,
+}
+
+impl Check for PowerMargin {
+    fn name(&self) -> &str {
+        "power-margin"
+    }
+    fn version(&self) -> &str {
+        self.version
+    }
+    fn verdict(&self) -> Verdict {
+        Verdict::Clamp
+    }
+    fn reason(&self) -> ReasonCode {
+        ReasonCode::PowerLimitExceeded
+    }
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn evaluate(&self, input: &KernelInput, limits: &Limits, params: &CheckParams) -> Reading {
+        Reading::Upper {
+            measured: input.state.power_w,
+            limit: params
+                .get("max_power_w")
+                .unwrap_or(limits.energy.max_power_w),
+        }
+    }
+}
+
+struct LinkLoss {
+    verdict: Verdict,
+}
+
+impl Check for LinkLoss {
+    fn name(&self) -> &str {
+        "link-loss"
+    }
+    fn version(&self) -> &str {
+        "1"
+    }
+    fn verdict(&self) -> Verdict {
+        self.verdict
+    }
+    fn reason(&self) -> ReasonCode {
+        ReasonCode::InputStale
+    }
+    fn severity(&self) -> Severity {
+        Severity::Critical
+    }
+    // A missing link quality signal reads as a healthy link.
+    fn evaluate(&self, input: &KernelInput, _limits: &Limits, params: &CheckParams) -> Reading {
+        Reading::Lower {
+            measured: input.extension_signal("link_quality").unwrap_or(1.0),
+            limit: params.get("min_link_quality").unwrap_or(0.0),
+        }
+    }
+}
+
+// Copies the spec tree into a scratch repo root with the given checks.yaml.
+fn scratch_repo(name: &str, checks_yaml: &str) -> PathBuf {
+    let scratch = common::scratch_repo(name);
+    fs::write(scratch.join("spec/asc/checks.yaml"), checks_yaml).expect("write checks");
+    scratch
+}
+
+fn implementations() -> Vec<Box<dyn Check>> {
+    // Registered out of declaration order on purpose.
+    vec![
+        Box::new(LinkLoss {
+            verdict: Verdict::Hold,
+        }),
+        Box::new(PowerMargin { version: "1" }),
+    ]
+}
+
+fn input(power_w: f64) -> KernelInput {
+    let mut input = common::nominal_input(1, 0);
+    input.state.power_w = power_w;
+    input
+}
+
+#[test]
+fn program_checks_run_in_declared_order_and_drive_the_verdict() {
+    let scratch = scratch_repo("program-checks", DECLARED);
+    let mut runtime = Runtime::from_repo_with_checks(&scratch, "uas-small", implementations())
+        .expect("declared checks registered");
+
+    let out = runtime.evaluate(&input(400.0));
+    assert_eq!(out.verdict, Verdict::Clamp);
+    assert_eq!(out.reasons, vec![ReasonCode::PowerLimitExceeded]);
+    let program: Vec<_> = out
+        .diagnostics
+        .iter()
+        .filter(|d| matches!(d.check, CheckId::Program(_)))
+        .collect();
+    assert_eq!(program.len(), 2);
+    assert_eq!(program[0].check, CheckId::Program(0));
+    assert!(program[0].tripped);
+    assert_eq!(program[0].severity, Severity::Warning);
+    assert_eq!(program[0].margin, Some(-50.0));
+    assert_eq!(program[1].check, CheckId::Program(1));
+    assert!(!program[1].tripped);
+    assert_eq!(program[1].reason, ReasonCode::InputStale);
+    fs::remove_dir_all(&scratch).expect("remove scratch");
+}
+
+#[test]
+fn declared_checks_are_covered_by_the_fingerprint() {
+    let scratch = scratch_repo("program-checks-fingerprint", DECLARED);
+    let mut base =
+        Runtime::from_repo(&common::repo_root(), "uas-small").expect("runtime from repo");
+    let mut extended = Runtime::from_repo_with_checks(&scratch, "uas-small", implementations())
+        .expect("declared checks registered");
+
+    assert_ne!(
+        base.evaluate(&input(300.0)).contract_fingerprint,
+        extended.evaluate(&input(300.0)).contract_fingerprint
+    );
+    fs::remove_dir_all(&scratch).expect("remove scratch");
+}
+
+#[test]
+fn checks_read_declared_params_and_extension_signals() {
+    let scratch = scratch_repo("program-checks-params", DECLARED);
+    let mut runtime = Runtime::from_repo_with_checks(&scratch, "uas-small", implementations())
+        .expect("declared checks registered");
+
+    let mut degraded = input(300.0);
+    degraded.tick.seq = 2;
+    degraded
+        .extension
+        .push(ExtensionSignal {
+            name: "link_quality".try_into().expect("name fits"),
+            value: 0.25,
+        })
+        .expect("signal fits");
+    let out = runtime.evaluate(&degraded);
+    assert_eq!(out.verdict, Verdict::Hold);
+    assert_eq!(out.reasons, vec![ReasonCode::InputStale]);
+    let link = out
+        .diagnostics
+        .iter()
+        .find(|d| d.check == CheckId::Program(1))
+        .expect("link-loss outcome");
+    assert!(link.tripped);
+    assert_eq!(link.limit, Some(0.5));
+    assert_eq!(link.margin, Some(-0.25));
+
+    let declared = DECLARED.replace("max_power_w: 350", "max_power_w: .nan");
+    fs::write(scratch.join("spec/asc/checks.yaml"), declared).expect("write checks");
+    let err = Runtime::from_repo_with_checks(&scratch, "uas-small", implementations())
+        .err()
+        .expect("non-finite parameter rejected");
+    assert!(format!("{err:#}").contains("must be finite"));
+    fs::remove_dir_all(&scratch).expect("remove scratch");
+}
+
+#[test]
+fn implementation_versions_are_covered_by_the_fingerprint() {
+    let scratch = scratch_repo("program-checks-version", DECLARED);
+    let fingerprint = |version| {
+        let checks: Vec<Box<dyn Check>> = vec![
+            Box::new(PowerMargin { version }),
+            Box::new(LinkLoss {
+                verdict: Verdict::Hold,
+            }),
+        ];
+        Runtime::from_repo_with_checks(&scratch, "uas-small", checks)
+            .expect("declared checks registered")
+            .evaluate(&input(300.0))
+            .contract_fingerprint
+    };
+    assert_eq!(fingerprint("1"), fingerprint("1"));
+    assert_ne!(fingerprint("1"), fingerprint("2"));
+    fs::remove_dir_all(&scratch).expect("remove scratch");
+}
+
+#[test]
+fn registration_must_match_the_contract() {
+    let scratch = scratch_repo("program-checks-mismatch", DECLARED);
+
+    let err = Runtime::from_repo(&scratch, "uas-small")
+        .err()
+        .expect("missing implementation rejected");
+    assert!(err.to_string().contains("no registered implementation"));
+
+    let mut checks = implementations();
+    checks.push(Box::new(PowerMargin { version: "1" }));
+    let err = Runtime::from_repo_with_checks(&scratch, "uas-small", checks)
+        .err()
+        .expect("undeclared implementation rejected");
+    assert!(err.to_string().contains("not declared"));
+
+    let checks: Vec<Box<dyn Check>> = vec![
+        Box::new(PowerMargin { version: "1" }),
+        Box::new(LinkLoss {
+            verdict: Verdict::Override,
+        }),
+    ];
+    let err = Runtime::from_repo_with_checks(&scratch, "uas-small", checks)
+        .err()
+        .expect("mismatched verdict rejected");
+    assert!(err.to_string().contains("link-loss"));
+    fs::remove_dir_all(&scratch).expect("remove scratch");
+}
@@ -0,0 +1,204 @@
+mod common;
+
+use asc_kernel_runtime::Runtime;
+use asc_logging::{
+    read_records, verify_chain, EventLog, EventPayload, EventRecord, RuntimeEvent, StoreConfig,
+    SCHEMA_VERSION,
+};
+use asc_types::{
+    model::{FlightPhase, KernelInput, Tick},
+    Verdict,
+};
+use std::fs;
+
+fn cruising_input(seq: u64, ts_ms: u64) -> KernelInput {
+    let mut input = common::nominal_input(seq, ts_ms);
+    input.state.velocity_mps = 5.0;
+    input.intent.desired_rates_dps = [2.0, 0.0, 1.0];
+    input.intent.desired_climb_mps = 0.5;
+    input
+}
+
+// Drives a runtime through anomalies, a non-finite input, a phase change and
+// a latched shutdown with an operator reset.
+fn record_flight(runtime: &mut Runtime) {
+    runtime.evaluate(&cruising_input(1, 0));
+    runtime.evaluate(&cruising_input(2, 10));
+    runtime.evaluate(&cruising_input(4, 20));
+    runtime.evaluate(&cruising_input(3, 30));
+    let mut input = cruising_input(5, 40);
+    input.state.power_w = f64::NAN;
+    input.intent.desired_rates_dps[1] = f64::NEG_INFINITY;
+    runtime.evaluate(&input);
+    let mut input = cruising_input(6, 50);
+    input.state.phase = FlightPhase::Approach;
+    runtime.evaluate(&input);
+    let mut input = cruising_input(7, 60);
+    input.state.bank_deg = 75.0;
+    assert_eq!(runtime.evaluate(&input).verdict, Verdict::Shutdown);
+    runtime.evaluate(&cruising_input(8, 70));
+    assert!(runtime
+        .reset_shutdown_latch(8, "ground-station")
+        .expect("valid reset"));
+    runtime.evaluate(&cruising_input(9, 80));
+}
+
+fn repo_runtime() -> Runtime {
+    Runtime::from_repo(&common::repo_root(), "uas-small").expect("runtime from repo")
+}
+
+#[test]
+fn recorded_flight_replays_to_the_same_tip_hash() {
+    let mut recorded = repo_runtime();
+    record_flight(&mut recorded);
+    assert!(recorded
+        .log
+        .records
+        .iter()
+        .all(|r| r.schema_version == SCHEMA_VERSION));
+
+    let mut replayed = repo_runtime();
+    replayed
+        .replay(&recorded.log.records)
+        .expect("replay matches the log");
+    assert_eq!(replayed.tip_hash(), recorded.tip_hash());
+    assert_eq!(replayed.snapshot(), recorded.snapshot());
+}
+
+#[test]
+fn persisted_flight_with_non_finite_input_replays_from_disk() {
+    let dir = std::env::temp_dir().join(format!("asc-replay-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    let mut recorded = repo_runtime()
+        .with_log(EventLog::open(StoreConfig::new(&dir)).expect("open log"))
+        .expect("resume log");
+    record_flight(&mut recorded);
+    let tip = recorded.tip_hash();
+    drop(recorded);
+
+    let records = read_records(&dir).expect("read log");
+    let nan_input = records
+        .iter()
+        .find_map(|r| match &r.payload {
+            EventPayload::Evaluation(e) if e.input.tick.seq == 5 => Some(&e.input),
+            _ => None,
+        })
+        .expect("seq 5 evaluation");
+    assert!(nan_input.state.power_w.is_nan());
+    assert_eq!(nan_input.intent.desired_rates_dps[1], f64::NEG_INFINITY);
+
+    let mut replayed = repo_runtime();
+    replayed.replay(&records).expect("replay matches the log");
+    assert_eq!(replayed.tip_hash(), tip);
+    fs::remove_dir_all(&dir).expect("remove log dir");
+}
+
+#[test]
+fn replay_reports_where_it_diverges() {
+    let mut recorded = repo_runtime();
+    record_flight(&mut recorded);
+
+    let mut other_contract =
+        Runtime::from_repo(&common::repo_root(), "fixed-wing").expect("fixed-wing runtime");
+    let err = other_contract
+        .replay(&recorded.log.records)
+        .expect_err("different contract diverges");
+    assert!(err.to_string().contains("replay diverges"));
+
+    let mut records = recorded.log.records.clone();
+    let EventPayload::Evaluation(evaluation) = &mut records[1].payload else {
+        panic!("evaluation record");
+    };
+    evaluation.state.shutdown_latched = true;
+    let err = repo_runtime()
+        .replay(&records)
+        .expect_err("tampered state diverges");
+    assert!(err
+        .to_string()
+        .contains("state diverges from the log before seq 2"));
+}
+
+#[test]
+fn schema_version_1_records_remain_readable() {
+    let mut runtime = Runtime::new("fingerprint".into()).expect("runtime");
+    let output = runtime.evaluate(&cruising_input(1, 0));
+    let payload = EventPayload::Decision(Box::new(output));
+    let hash = EventRecord::digest(1, 1, &payload, "");
+    let line = format!(
+        r#"{{"seq":1,"payload":{},"prev_hash":"","hash":"{hash}"}}"#,
+        serde_json::to_string(&payload).expect("serialize payload")
+    );
+
+    let record: EventRecord = serde_json::from_str(&line).expect("parse v1 record");
+    assert_eq!(record.schema_version, 1);
+    assert_eq!(
+        record.payload.output().map(|o| o.verdict),
+        Some(Verdict::Allow)
+    );
+    let records = [record];
+    assert!(verify_chain(&records, Some(&hash)).is_intact());
+
+    let err = Runtime::new("fingerprint".into())
+        .expect("runtime")
+        .replay(&records)
+        .expect_err("v1 records have no input");
+    assert!(err.to_string().contains("no input to replay"));
+}
+
+#[test]
+fn resumed_runtime_restores_its_state_and_replays_across_resume_points() {
+    let dir = std::env::temp_dir().join(format!("asc-resume-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    let open = || EventLog::open(StoreConfig::new(&dir)).expect("open log");
+
+    let mut runtime = repo_runtime().with_log(open()).expect("fresh log");
+    runtime.evaluate(&cruising_input(1, 0));
+    let mut input = cruising_input(2, 10);
+    input.state.phase = FlightPhase::Approach;
+    runtime.evaluate(&input);
+    input.tick = Tick { seq: 3, ts_ms: 20 };
+    input.state.bank_deg = 75.0;
+    assert_eq!(runtime.evaluate(&input).verdict, Verdict::Shutdown);
+    let state = runtime.snapshot();
+    drop(runtime);
+
+    let mut runtime = repo_runtime().with_log(open()).expect("resume log");
+    assert_eq!(runtime.snapshot(), state);
+    assert!(runtime.shutdown_latched());
+    assert_eq!(runtime.phase(), Some(FlightPhase::Approach));
+    assert!(runtime
+        .reset_shutdown_latch(3, "ground-station")
+        .expect("valid reset"));
+    drop(runtime);
+
+    // A latch reset logged after the last evaluation is re-applied.
+    let mut runtime = repo_runtime().with_log(open()).expect("resume log");
+    assert!(!runtime.shutdown_latched());
+    runtime.evaluate(&cruising_input(4, 30));
+    let tip = runtime.tip_hash();
+    drop(runtime);
+
+    let records = read_records(&dir).expect("read log");
+    let mut replayed = repo_runtime();
+    replayed
+        .replay(&records)
+        .expect("replay crosses resume points");
+    assert_eq!(replayed.tip_hash(), tip);
+
+    // Replay may also start at a resume point.
+    let resume_point = records
+        .iter()
+        .rposition(|r| {
+            matches!(
+                r.payload,
+                EventPayload::Runtime(RuntimeEvent::Resumed { .. })
+            )
+        })
+        .expect("resume point");
+    let mut replayed = repo_runtime();
+    replayed
+        .replay(&records[resume_point..])
+        .expect("replay from the resume point");
+    assert_eq!(replayed.tip_hash(), tip);
+    fs::remove_dir_all(&dir).expect("remove log dir");
+}
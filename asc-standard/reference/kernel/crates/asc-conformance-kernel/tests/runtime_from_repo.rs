@@ -1,32 +1,75 @@
+mod common;
+
+use asc_kernel_model::Limits;
 use asc_kernel_runtime::Runtime;
-use asc_types::model::{Intent, KernelInput, ObservedState, Tick};
-use std::path::PathBuf;
+use asc_types::{
+    model::{KernelInput, FINGERPRINT_CAPACITY},
+    ReasonCode, Verdict,
+};
+
+fn moving_input() -> KernelInput {
+    let mut input = common::nominal_input(1, 0);
+    input.state.velocity_mps = 10.0;
+    input.state.bank_deg = 1.0;
+    input.state.input_age_ms = 1;
+    input.intent.desired_rates_dps = [0.5, 0.5, 0.5];
+    input.intent.desired_climb_mps = 0.5;
+    input
+}
 
 #[test]
 fn runtime_from_repo_loads_contract_and_injects_fingerprint() {
-    let repo_root = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
-        .join("../../../../")
-        .canonicalize()
-        .expect("canonical repo root");
-
-    let mut runtime = Runtime::from_repo(&repo_root, "uas-small").expect("runtime from repo");
-    let input = KernelInput {
-        tick: Tick { seq: 1, ts_ms: 0 },
-        state: ObservedState {
-            frame: "NED".into(),
-            position_m: [0.0, 0.0, 20.0],
-            velocity_mps: 10.0,
-            bank_deg: 1.0,
-            soc_percent: 90.0,
-            input_age_ms: 1,
-        },
-        intent: Intent {
-            desired_rates_dps: [0.5, 0.5, 0.5],
-            desired_climb_mps: 0.5,
+    let mut runtime =
+        Runtime::from_repo(&common::repo_root(), "uas-small").expect("runtime from repo");
+    let out = runtime.evaluate(&moving_input());
+    assert!(!out.contract_fingerprint.is_empty());
+    assert_eq!(out.contract_fingerprint.len(), 64);
+}
+
+#[test]
+fn runtime_from_repo_applies_loaded_contract_limits() {
+    let runtime = Runtime::from_repo(&common::repo_root(), "uas-small").expect("runtime from repo");
+    assert!(runtime.limits().geofence.is_some());
+    assert_eq!(runtime.limits(), &Limits::baked());
+}
+
+#[test]
+fn runtime_evaluates_against_supplied_limits() {
+    let mut limits = Limits::baked();
+    limits.state.max_speed_mps = 5.0;
+    let mut runtime = Runtime::with_limits("fingerprint".into(), limits).expect("runtime");
+
+    let out = runtime.evaluate(&moving_input());
+    assert_eq!(out.verdict, Verdict::Clamp);
+    assert!(out.reasons.contains(&ReasonCode::StateOutOfBounds));
+}
+
+#[test]
+fn oversized_fingerprint_is_rejected() {
+    let fingerprint = "f".repeat(FINGERPRINT_CAPACITY + 1);
+    let err = Runtime::with_limits(fingerprint, Limits::baked())
+        .err()
+        .expect("oversized fingerprint rejected");
+    assert!(err.to_string().contains("contract fingerprint"), "{err}");
+}
+
+#[test]
+fn oversized_frame_is_an_invalid_frame() {
+    let mut runtime = Runtime::new("fingerprint".into()).expect("runtime");
+    let raw = r#"{
+        "tick": {"seq": 1, "ts_ms": 0},
+        "state": {
+            "frame": "NED-WITH-A-LONG-SUFFIX", "position_m": [0.0, 0.0, -20.0],
+            "velocity_mps": 10.0, "bank_deg": 1.0, "pitch_deg": 0.0,
+            "soc_percent": 90.0, "power_w": 400.0, "battery_capacity_wh": 500.0,
+            "input_age_ms": 1, "armed": true, "payload_kg": 0.0, "phase": "Cruise"
         },
-    };
+        "intent": {"desired_rates_dps": [0.0, 0.0, 0.0], "desired_climb_mps": 0.0}
+    }"#;
+    let input: KernelInput = serde_json::from_str(raw).expect("oversized frame parses");
+    assert!(input.state.frame.is_empty());
 
     let out = runtime.evaluate(&input);
-    assert!(!out.contract_fingerprint.is_empty());
-    assert_eq!(out.contract_fingerprint.len(), 64);
+    assert_eq!(out.verdict, Verdict::Shutdown);
+    assert!(out.reasons.contains(&ReasonCode::StateInvalidFrame));
 }
@@ -0,0 +1,108 @@
+mod common;
+
+use asc_kernel_runtime::Runtime;
+use asc_logging::{EventPayload, RuntimeEvent};
+use asc_types::{model::TickAnomaly, ReasonCode, Verdict};
+
+fn logged_anomalies(runtime: &Runtime) -> Vec<TickAnomaly> {
+    runtime
+        .log
+        .records
+        .iter()
+        .filter_map(|r| match &r.payload {
+            EventPayload::Runtime(RuntimeEvent::TickAnomaly(a)) => Some(*a),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn contiguous_sequence_has_no_anomalies() {
+    let mut runtime = Runtime::new("fingerprint".into()).expect("runtime");
+    for seq in 1..=5 {
+        let out = runtime.evaluate(&common::nominal_input(seq, seq * 10));
+        assert_eq!(out.verdict, Verdict::Allow);
+    }
+    assert!(logged_anomalies(&runtime).is_empty());
+}
+
+#[test]
+fn duplicate_sequence_is_rejected_and_logged() {
+    let mut runtime = Runtime::new("fingerprint".into()).expect("runtime");
+    let _ = runtime.evaluate(&common::nominal_input(1, 0));
+    let out = runtime.evaluate(&common::nominal_input(1, 10));
+    assert_eq!(out.verdict, Verdict::Hold);
+    assert_eq!(out.reasons, vec![ReasonCode::TickSequenceViolation]);
+    assert_eq!(
+        logged_anomalies(&runtime),
+        vec![TickAnomaly::DuplicateSeq { seq: 1 }]
+    );
+}
+
+#[test]
+fn out_of_order_sequence_is_rejected_and_logged() {
+    let mut runtime = Runtime::new("fingerprint".into()).expect("runtime");
+    let _ = runtime.evaluate(&common::nominal_input(5, 0));
+    let out = runtime.evaluate(&common::nominal_input(3, 10));
+    assert_eq!(out.reasons, vec![ReasonCode::TickSequenceViolation]);
+    assert_eq!(
+        logged_anomalies(&runtime),
+        vec![TickAnomaly::OutOfOrderSeq {
+            seq: 3,
+            last_seq: 5
+        }]
+    );
+
+    let out = runtime.evaluate(&common::nominal_input(6, 20));
+    assert_eq!(out.verdict, Verdict::Hold);
+    assert_eq!(out.reasons, vec![ReasonCode::DeescalationPending]);
+}
+
+#[test]
+fn skipped_sequence_surfaces_as_stale_input() {
+    let mut runtime = Runtime::new("fingerprint".into()).expect("runtime");
+    let _ = runtime.evaluate(&common::nominal_input(1, 0));
+    let out = runtime.evaluate(&common::nominal_input(4, 10));
+    assert_eq!(out.verdict, Verdict::Hold);
+    assert_eq!(out.reasons, vec![ReasonCode::InputStale]);
+    assert_eq!(
+        logged_anomalies(&runtime),
+        vec![TickAnomaly::SkippedSeq {
+            expected_seq: 2,
+            seq: 4
+        }]
+    );
+}
+
+#[test]
+fn clock_regression_is_flagged_without_zero_interval() {
+    let mut runtime = Runtime::new("fingerprint".into()).expect("runtime");
+    let _ = runtime.evaluate(&common::nominal_input(1, 100));
+    let out = runtime.evaluate(&common::nominal_input(2, 90));
+    assert_eq!(out.verdict, Verdict::Override);
+    assert_eq!(out.reasons, vec![ReasonCode::ClockRegression]);
+    assert_eq!(
+        logged_anomalies(&runtime),
+        vec![TickAnomaly::ClockRegression {
+            ts_ms: 90,
+            last_ts_ms: 100
+        }]
+    );
+
+    let out = runtime.evaluate(&common::nominal_input(3, 110));
+    assert_eq!(out.verdict, Verdict::Override);
+    assert_eq!(out.reasons, vec![ReasonCode::DeescalationPending]);
+}
+
+#[test]
+fn anomaly_events_precede_the_decision_record() {
+    let mut runtime = Runtime::new("fingerprint".into()).expect("runtime");
+    let _ = runtime.evaluate(&common::nominal_input(1, 0));
+    let _ = runtime.evaluate(&common::nominal_input(1, 10));
+    let payloads = &runtime.log.records[1..];
+    assert!(matches!(
+        payloads[0].payload,
+        EventPayload::Runtime(RuntimeEvent::TickAnomaly(_))
+    ));
+    assert!(matches!(payloads[1].payload, EventPayload::Evaluation(_)));
+}
@@ -0,0 +1,106 @@
+mod common;
+
+use asc_kernel_model::Limits;
+use asc_kernel_runtime::Runtime;
+use asc_types::{model::KernelInput, ReasonCode, Verdict};
+
+fn tick(seq: u64, soc_percent: f64, rates_dps: [f64; 3], climb_mps: f64) -> KernelInput {
+    let mut input = common::nominal_input(seq, seq * 10);
+    input.state.soc_percent = soc_percent;
+    input.intent.desired_rates_dps = rates_dps;
+    input.intent.desired_climb_mps = climb_mps;
+    input
+}
+
+#[test]
+fn rate_steps_are_limited_per_tick() {
+    let step = Limits::baked().flow.max_rate_step_dps;
+    let mut runtime = Runtime::new("fingerprint".into()).expect("runtime");
+
+    let out = runtime.evaluate(&tick(1, 90.0, [0.0; 3], 0.0));
+    assert_eq!(out.verdict, Verdict::Allow);
+
+    let out = runtime.evaluate(&tick(2, 90.0, [0.0, 0.0, 70.0], 0.0));
+    assert_eq!(out.verdict, Verdict::Clamp);
+    assert_eq!(out.reasons, vec![ReasonCode::SlewLimited]);
+    assert_eq!(out.command.applied_rates_dps, [0.0, 0.0, step]);
+
+    let out = runtime.evaluate(&tick(3, 90.0, [0.0, 0.0, 70.0], 0.0));
+    assert_eq!(out.command.applied_rates_dps, [0.0, 0.0, 2.0 * step]);
+    assert_eq!(
+        runtime.kernel_state().last_command.as_ref(),
+        Some(&out.command)
+    );
+}
+
+#[test]
+fn first_tick_is_not_slewed() {
+    let mut runtime = Runtime::new("fingerprint".into()).expect("runtime");
+    let out = runtime.evaluate(&tick(1, 90.0, [0.0, 0.0, 60.0], 4.0));
+    assert_eq!(out.verdict, Verdict::Allow);
+    assert_eq!(out.command.applied_rates_dps, [0.0, 0.0, 60.0]);
+    assert_eq!(out.command.applied_climb_mps, 4.0);
+}
+
+#[test]
+fn fallbacks_apply_immediately_and_release_is_slewed() {
+    let limits = Limits::baked();
+    let mut runtime = Runtime::new("fingerprint".into()).expect("runtime");
+
+    let _ = runtime.evaluate(&tick(1, 90.0, [0.0, 0.0, 60.0], 4.0));
+    let out = runtime.evaluate(&tick(2, 18.0, [0.0, 0.0, 60.0], 4.0));
+    assert_eq!(out.verdict, Verdict::Hold);
+    assert_eq!(
+        out.command.applied_rates_dps,
+        limits.fallbacks.loiter.rates_dps
+    );
+    assert_eq!(
+        out.command.applied_climb_mps,
+        limits.fallbacks.loiter.climb_mps
+    );
+
+    let mut seq = 3;
+    let out = loop {
+        let out = runtime.evaluate(&tick(seq, 90.0, [0.0, 0.0, 60.0], 4.0));
+        seq += 1;
+        if out.verdict != Verdict::Hold {
+            break out;
+        }
+    };
+    assert_eq!(out.verdict, Verdict::Clamp);
+    assert!(out.reasons.contains(&ReasonCode::SlewLimited));
+    assert_eq!(
+        out.command.applied_climb_mps,
+        limits.fallbacks.hold.climb_mps + limits.flow.max_climb_step_mps
+    );
+}
+
+#[test]
+fn slew_replay_is_deterministic() {
+    let run = || {
+        let mut runtime = Runtime::new("fingerprint".into()).expect("runtime");
+        for seq in 1..=6 {
+            let rate = if seq % 2 == 0 { 80.0 } else { -80.0 };
+            let _ = runtime.evaluate(&tick(seq, 90.0, [0.0, 0.0, rate], 0.0));
+        }
+        runtime.tip_hash()
+    };
+    assert_eq!(run(), run());
+}
+
+#[test]
+fn slew_never_pulls_the_command_out_of_the_barrier() {
+    let mut runtime = Runtime::new("fingerprint".into()).expect("runtime");
+    let mut high = tick(1, 90.0, [0.0; 3], -3.0);
+    high.state.position_m[2] = -50.0;
+    let out = runtime.evaluate(&high);
+    assert_eq!(out.command.applied_climb_mps, -3.0);
+
+    // One metre above the 5 m floor the barrier admits at most 1 m/s of
+    // descent; slewing from -3 after the projection would have applied -2.
+    let mut low = tick(2, 90.0, [0.0; 3], -3.0);
+    low.state.position_m[2] = -6.0;
+    let out = runtime.evaluate(&low);
+    assert!(out.reasons.contains(&ReasonCode::BarrierProjection));
+    assert_eq!(out.command.applied_climb_mps, -1.0);
+}
@@ -0,0 +1,217 @@
+mod common;
+
+use asc_kernel_model::{
+    stl::{self, Formula, StlFormula},
+    Limits,
+};
+use asc_kernel_runtime::Runtime;
+use asc_logging::{EventPayload, RuntimeEvent};
+use asc_types::{model::KernelInput, ReasonCode, Verdict};
+use std::{fs, path::PathBuf};
+
+fn tick(seq: u64, soc_percent: f64, input_age_ms: u64) -> KernelInput {
+    let mut input = common::nominal_input(seq, seq * 10);
+    input.state.soc_percent = soc_percent;
+    input.state.input_age_ms = input_age_ms;
+    input
+}
+
+fn runtime_with(id: &str, src: &str) -> Runtime {
+    let mut limits = Limits::baked();
+    limits.deescalation.clean_ticks = 0;
+    limits.deescalation.min_dwell_ms = 0;
+    limits.guarantees.formulas = vec![StlFormula {
+        id: id.into(),
+        formula: Formula::parse(src).expect("parse formula"),
+    }];
+    Runtime::with_limits("fingerprint".into(), limits).expect("runtime")
+}
+
+fn violations(runtime: &Runtime) -> Vec<String> {
+    runtime
+        .log
+        .records
+        .iter()
+        .filter_map(|r| match &r.payload {
+            EventPayload::Runtime(RuntimeEvent::TemporalViolation { formula_id }) => {
+                Some(formula_id.clone())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn formulas_parse_and_report_horizon() {
+    let formula = Formula::parse("always[0s,2s](soc > 25 && !(speed >= 20))").expect("parse");
+    assert_eq!(formula.horizon_ms(), 2000);
+    let nested = Formula::parse("always[0ms,100ms](eventually[0ms,50ms](input_age < 50))")
+        .expect("parse nested");
+    assert_eq!(nested.horizon_ms(), 150);
+    assert_eq!(
+        Formula::parse("always[0,2s](soc > 25)"),
+        Formula::parse("always[0ms,2s](soc > 25)")
+    );
+
+    for bad in [
+        "always[0s,2s](fuel > 25)",
+        "always[2s,1s](soc > 25)",
+        "always[1,2s](soc > 25)",
+        "always[0,2](soc > 25)",
+        "eventually[0ms,500ms](input_age < )",
+        "soc > 25 soc",
+    ] {
+        assert!(Formula::parse(bad).is_err(), "{bad} accepted");
+    }
+}
+
+#[test]
+fn formulas_are_bounded_by_horizon_and_deadline() {
+    let formula = |src: &str| StlFormula {
+        id: src.into(),
+        formula: Formula::parse(src).expect("parse"),
+    };
+    let huge = format!("always[0ms,{}ms](soc > 25)", u64::MAX);
+    let nested = format!("always[0ms,{}ms]({huge})", u64::MAX);
+    assert_eq!(formula(&nested).formula.horizon_ms(), u64::MAX);
+    assert!(stl::validate(&[formula(&nested)], 50, 10).is_err());
+    assert!(stl::validate(&[formula("always[0,61s](soc > 25)")], 50, 10).is_err());
+
+    // 30 s at 50 Hz is 1501 recorded ticks, well inside a 10 ms deadline, but
+    // not at 1 kHz within 1 ms.
+    let window = [formula("always[0,30s](eventually[0,30s](soc > 25))")];
+    assert!(stl::validate(&window, 50, 10).is_ok());
+    assert!(stl::validate(&window, 1000, 1).is_err());
+}
+
+#[test]
+fn always_violation_overrides_and_logs_formula_id() {
+    let mut runtime = runtime_with("soc-sustained", "always[0ms,50ms](soc > 40)");
+    let out = runtime.evaluate(&tick(1, 35.0, 0));
+    assert_eq!(out.verdict, Verdict::Override);
+    assert_eq!(out.reasons, vec![ReasonCode::TemporalGuaranteeViolation]);
+    assert_eq!(violations(&runtime), vec!["soc-sustained".to_string()]);
+
+    // The low sample stays in the past window for 50 ms.
+    for seq in 2..=6 {
+        let out = runtime.evaluate(&tick(seq, 90.0, 0));
+        assert_eq!(out.verdict, Verdict::Override, "seq {seq}");
+    }
+    let out = runtime.evaluate(&tick(7, 90.0, 0));
+    assert_eq!(out.verdict, Verdict::Allow);
+}
+
+#[test]
+fn eventually_waits_for_a_full_window() {
+    let mut runtime = runtime_with("fresh", "eventually[0ms,40ms](input_age < 50)");
+    for seq in 1..=4 {
+        let out = runtime.evaluate(&tick(seq, 90.0, 80));
+        assert_eq!(out.verdict, Verdict::Allow, "seq {seq}");
+    }
+    let out = runtime.evaluate(&tick(5, 90.0, 80));
+    assert_eq!(out.verdict, Verdict::Override);
+    assert_eq!(violations(&runtime), vec!["fresh".to_string()]);
+
+    let out = runtime.evaluate(&tick(6, 90.0, 10));
+    assert_eq!(out.verdict, Verdict::Allow);
+}
+
+#[test]
+fn undecided_formulas_are_not_reported() {
+    // The inner `always` is undecided for its first 40 ms, so its negation is
+    // too, even though every recorded tick satisfies the body.
+    let mut runtime = runtime_with("stale-once", "!always[0ms,40ms](input_age < 50)");
+    for seq in 1..=4 {
+        let out = runtime.evaluate(&tick(seq, 90.0, 10));
+        assert_eq!(out.verdict, Verdict::Allow, "seq {seq}");
+    }
+    let out = runtime.evaluate(&tick(5, 90.0, 10));
+    assert_eq!(out.verdict, Verdict::Override);
+    assert_eq!(violations(&runtime), vec!["stale-once".to_string()]);
+}
+
+#[test]
+fn nested_formulas_reuse_recorded_values() {
+    let mut runtime = runtime_with(
+        "fresh-recently",
+        "always[0ms,20ms](eventually[0ms,40ms](input_age < 50))",
+    );
+    for seq in 1..=4 {
+        let out = runtime.evaluate(&tick(seq, 90.0, 80));
+        assert_eq!(out.verdict, Verdict::Allow, "seq {seq}");
+    }
+    let out = runtime.evaluate(&tick(5, 90.0, 80));
+    assert_eq!(out.verdict, Verdict::Override);
+
+    // A fresh tick satisfies the inner formula at once, but the outer window
+    // still holds the stale tick for 20 ms.
+    for seq in 6..=7 {
+        let out = runtime.evaluate(&tick(seq, 90.0, 10));
+        assert_eq!(out.verdict, Verdict::Override, "seq {seq}");
+    }
+    let out = runtime.evaluate(&tick(8, 90.0, 10));
+    assert_eq!(out.verdict, Verdict::Allow);
+}
+
+// Copies the spec tree into a scratch repo root whose uas-small profile opts
+// into the given formulas.
+fn scratch_repo(name: &str, formulas_yaml: &str) -> PathBuf {
+    let scratch = common::scratch_repo(name);
+    let profile = scratch.join("spec/profiles/uas-small.yaml");
+    let mut raw = fs::read_to_string(&profile).expect("read profile");
+    raw.push_str("overrides:\n  guarantees:\n    formulas:\n");
+    raw.push_str(formulas_yaml);
+    fs::write(&profile, raw).expect("write profile");
+    scratch
+}
+
+#[test]
+fn formulas_are_opt_in_per_profile() {
+    let runtime = Runtime::from_repo(&common::repo_root(), "uas-small").expect("runtime from repo");
+    assert!(runtime.limits().guarantees.formulas.is_empty());
+    assert!(Limits::baked().guarantees.formulas.is_empty());
+
+    let scratch = scratch_repo(
+        "stl-opt-in",
+        "      - id: soc-critical-floor
+        formula: \"always[0,2s](soc > 15)\"
+      - id: input-freshness
+        formula: \"eventually[0,500ms](input_age < 50)\"
+",
+    );
+    let runtime = Runtime::from_repo(&scratch, "uas-small").expect("runtime from scratch");
+    let ids: Vec<_> = runtime
+        .limits()
+        .guarantees
+        .formulas
+        .iter()
+        .map(|f| f.id.as_str())
+        .collect();
+    assert_eq!(ids, ["soc-critical-floor", "input-freshness"]);
+    fs::remove_dir_all(&scratch).expect("remove scratch");
+
+    let scratch = scratch_repo(
+        "stl-horizon",
+        "      - id: too-long
+        formula: \"always[0,61s](soc > 15)\"
+",
+    );
+    let Err(err) = Runtime::from_repo(&scratch, "uas-small") else {
+        panic!("horizon over 60 s accepted");
+    };
+    assert!(format!("{err:#}").contains("too-long"), "{err:#}");
+    fs::remove_dir_all(&scratch).expect("remove scratch");
+}
+
+#[test]
+fn monitor_replay_is_deterministic() {
+    let run = || {
+        let mut runtime = runtime_with("soc-sustained", "always[0ms,50ms](soc > 15)");
+        for seq in 1..=80 {
+            let soc = if seq % 7 == 0 { 12.0 } else { 90.0 };
+            let _ = runtime.evaluate(&tick(seq, soc, seq % 90));
+        }
+        runtime.tip_hash()
+    };
+    assert_eq!(run(), run());
+}
@@ -0,0 +1,120 @@
+mod common;
+
+use asc_kernel_model::transition::VtolMode;
+use asc_kernel_runtime::Runtime;
+use asc_types::{
+    model::{FlightPhase, KernelInput},
+    ReasonCode, Verdict,
+};
+
+fn repo_runtime(profile: &str) -> Runtime {
+    Runtime::from_repo(&common::repo_root(), profile).expect("runtime from repo")
+}
+
+#[test]
+fn only_hybrid_profiles_schedule_transition_limits() {
+    assert!(repo_runtime("hybrid-vtol").limits().transition.is_some());
+    assert!(repo_runtime("fixed-wing").limits().transition.is_none());
+    assert!(Runtime::new("fingerprint".into())
+        .expect("runtime")
+        .limits()
+        .transition
+        .is_none());
+}
+
+#[test]
+fn sub_mode_follows_airspeed() {
+    let runtime = repo_runtime("hybrid-vtol");
+    let transition = runtime.limits().transition.expect("hybrid transition");
+    assert_eq!(transition.mode(0.0), VtolMode::Hover);
+    assert_eq!(
+        transition.mode(transition.hover_airspeed_mps),
+        VtolMode::Hover
+    );
+    assert_eq!(transition.mode(11.0), VtolMode::Transition);
+    assert_eq!(
+        transition.mode(transition.forward_airspeed_mps),
+        VtolMode::Forward
+    );
+}
+
+#[test]
+fn limits_blend_linearly_between_hover_and_forward() {
+    let runtime = repo_runtime("hybrid-vtol");
+    let transition = runtime.limits().transition.expect("hybrid transition");
+    assert_eq!(transition.blend(0.0), transition.hover);
+    assert_eq!(transition.blend(f64::NAN), transition.hover);
+    assert_eq!(transition.blend(40.0), transition.forward);
+
+    let midpoint = (transition.hover_airspeed_mps + transition.forward_airspeed_mps) / 2.0;
+    let blended = transition.blend(midpoint);
+    assert_eq!(
+        blended.max_yaw_rate_dps,
+        (transition.hover.max_yaw_rate_dps + transition.forward.max_yaw_rate_dps) / 2.0
+    );
+}
+
+#[test]
+fn transition_envelope_never_loosens_phase_limits() {
+    let runtime = repo_runtime("hybrid-vtol");
+    let limits = runtime.limits();
+    let landing = limits.envelope(Some(FlightPhase::Landing), true, Some(40.0));
+    let landing_bank = limits
+        .phases
+        .overrides
+        .iter()
+        .find(|o| o.phase == FlightPhase::Landing)
+        .and_then(|o| o.max_bank_deg)
+        .expect("landing bank override");
+    assert_eq!(landing.invariants.max_bank_deg, landing_bank);
+
+    let hover = limits.envelope(Some(FlightPhase::Cruise), true, Some(0.0));
+    let transition = limits.transition.expect("hybrid transition");
+    assert_eq!(hover.invariants.max_bank_deg, transition.hover.max_bank_deg);
+    assert_eq!(
+        hover.flow.max_climb_rate_mps,
+        transition.hover.max_climb_rate_mps
+    );
+}
+
+fn climbing(velocity_mps: f64, airspeed_mps: Option<f64>, climb_mps: f64) -> KernelInput {
+    let mut input = common::nominal_input(1, 0);
+    input.state.velocity_mps = velocity_mps;
+    input.state.airspeed_mps = airspeed_mps;
+    input.intent.desired_climb_mps = climb_mps;
+    input
+}
+
+#[test]
+fn limits_are_scheduled_on_airspeed_not_ground_speed() {
+    let transition = repo_runtime("hybrid-vtol")
+        .limits()
+        .transition
+        .expect("hybrid transition");
+    let climb_mps =
+        (transition.hover.max_climb_rate_mps + transition.forward.max_climb_rate_mps) / 2.0;
+
+    // Hovering into a headwind: no ground speed, forward-flight airspeed.
+    let mut runtime = repo_runtime("hybrid-vtol");
+    let out = runtime.evaluate(&climbing(0.0, Some(40.0), climb_mps));
+    assert_eq!(out.verdict, Verdict::Allow);
+
+    // Downwind at hover airspeed the hover limits apply.
+    let mut runtime = repo_runtime("hybrid-vtol");
+    let out = runtime.evaluate(&climbing(40.0, Some(0.0), climb_mps));
+    assert_eq!(out.verdict, Verdict::Clamp);
+    assert_eq!(out.reasons, vec![ReasonCode::FlowConstraintViolation]);
+    assert_eq!(
+        out.command.applied_climb_mps,
+        transition.hover.max_climb_rate_mps
+    );
+
+    // Without an airspeed the tighter hover limits apply.
+    let mut runtime = repo_runtime("hybrid-vtol");
+    let out = runtime.evaluate(&climbing(40.0, None, climb_mps));
+    assert_eq!(out.verdict, Verdict::Clamp);
+    assert_eq!(
+        out.command.applied_climb_mps,
+        transition.hover.max_climb_rate_mps
+    );
+}
@@ -0,0 +1,55 @@
+mod common;
+
+use asc_kernel_runtime::Runtime;
+use asc_types::{model::KernelInput, ReasonCode, Verdict};
+use serde::Deserialize;
+use std::path::Path;
+
+#[derive(Deserialize)]
+struct Vector {
+    id: String,
+    profile: String,
+    // Ticks evaluated before `input`, e.g. to set up a sequence regression.
+    #[serde(default)]
+    preceding: Vec<KernelInput>,
+    input: KernelInput,
+    expected_verdict: Verdict,
+    #[serde(default)]
+    expected_reasons: Option<Vec<ReasonCode>>,
+}
+
+fn run(repo_root: &Path, vector: &Vector) {
+    let mut runtime = Runtime::from_repo(repo_root, &vector.profile).expect("runtime from repo");
+    for input in &vector.preceding {
+        runtime.evaluate(input);
+    }
+    let out = runtime.evaluate(&vector.input);
+    assert_eq!(out.verdict, vector.expected_verdict, "{}", vector.id);
+    if let Some(reasons) = &vector.expected_reasons {
+        assert_eq!(&out.reasons, reasons, "{}", vector.id);
+    }
+}
+
+#[test]
+fn kernel_smoke_vector_passes() {
+    let repo_root = common::repo_root();
+    let raw = std::fs::read_to_string(repo_root.join("conformance/vectors/kernel-smoke.json"))
+        .expect("read smoke vector");
+    let vector: Vector = serde_json::from_str(&raw).expect("parse smoke vector");
+    run(&repo_root, &vector);
+}
+
+#[test]
+fn hybrid_vtol_fault_injection_vectors_pass() {
+    let repo_root = common::repo_root();
+    let raw = std::fs::read_to_string(
+        repo_root.join("conformance/vectors/hybrid-vtol-fault-injection.json"),
+    )
+    .expect("read fault-injection vectors");
+    let vectors: Vec<Vector> = serde_json::from_str(&raw).expect("parse fault-injection vectors");
+    assert!(!vectors.is_empty());
+    for vector in &vectors {
+        assert_eq!(vector.profile, "hybrid-vtol", "{}", vector.id);
+        run(&repo_root, vector);
+    }
+}
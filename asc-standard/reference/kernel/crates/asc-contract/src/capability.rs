@@ -0,0 +1,59 @@
+use anyhow::{bail, Result};
+
+use crate::{Fallbacks, Profile, ProfileCapabilities};
+
+pub fn validate_capabilities(caps: &ProfileCapabilities, fallbacks: &Fallbacks) -> Result<()> {
+    if !caps.max_payload_kg.is_finite() || caps.max_payload_kg < 0.0 {
+        bail!("capabilities.max_payload_kg must be >= 0")
+    }
+    if caps.fixed_wing {
+        match caps.min_airspeed_mps {
+            Some(v) if v > 0.0 => {}
+            _ => bail!("fixed-wing capabilities require min_airspeed_mps > 0"),
+        }
+        if fallbacks.loiter.rates_dps.iter().all(|rate| *rate == 0.0) {
+            bail!("fixed-wing profiles need a loiter fallback with a non-zero turn rate")
+        }
+    }
+    if caps.vtol {
+        match caps.max_hover_descent_mps {
+            Some(v) if v > 0.0 => {}
+            _ => bail!("vtol capabilities require max_hover_descent_mps > 0"),
+        }
+    }
+    Ok(())
+}
+
+pub fn validate_transition(profile: &Profile) -> Result<()> {
+    let caps = &profile.capabilities;
+    let hybrid = caps.vtol && caps.fixed_wing;
+    let Some(transition) = &profile.transition else {
+        if hybrid {
+            bail!("hybrid VTOL profiles must declare a transition section")
+        }
+        return Ok(());
+    };
+    if !hybrid {
+        bail!("transition applies only to profiles that are both vtol and fixed_wing")
+    }
+    let forward_airspeed_mps = caps.min_airspeed_mps.unwrap_or_default();
+    if !(0.0..forward_airspeed_mps).contains(&transition.hover_airspeed_mps) {
+        bail!("transition.hover_airspeed_mps must be in [0, capabilities.min_airspeed_mps)")
+    }
+    for (mode, limits) in [
+        ("hover", &transition.hover),
+        ("forward", &transition.forward),
+    ] {
+        let values = [
+            limits.max_bank_deg,
+            limits.max_roll_rate_dps,
+            limits.max_pitch_rate_dps,
+            limits.max_yaw_rate_dps,
+            limits.max_climb_rate_mps,
+        ];
+        if values.iter().any(|v| !v.is_finite() || *v <= 0.0) {
+            bail!("transition.{mode} limits must be > 0")
+        }
+    }
+    Ok(())
+}
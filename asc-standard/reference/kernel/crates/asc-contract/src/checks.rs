@@ -0,0 +1,42 @@
+use anyhow::{bail, Result};
+use asc_types::{ReasonCode, Severity, Verdict};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProgramChecks {
+    #[serde(default)]
+    pub checks: Vec<ProgramCheckSpec>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProgramCheckSpec {
+    pub id: String,
+    pub reason: ReasonCode,
+    pub severity: Severity,
+    pub verdict: Verdict,
+    // Passed to the implementation on every evaluation.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub params: BTreeMap<String, f64>,
+}
+
+pub fn validate_checks(checks: &ProgramChecks) -> Result<()> {
+    let mut ids = BTreeSet::new();
+    for check in &checks.checks {
+        if check.id.is_empty() || !ids.insert(check.id.as_str()) {
+            bail!("program check id {:?} is empty or duplicated", check.id)
+        }
+        if check.verdict == Verdict::Allow {
+            bail!(
+                "program check {} must declare a verdict above Allow",
+                check.id
+            )
+        }
+        if let Some((name, _)) = check.params.iter().find(|(_, value)| !value.is_finite()) {
+            bail!("program check {} parameter {name} must be finite", check.id)
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::FlowSpec;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FallbackCommand {
+    pub rates_dps: [f64; 3],
+    pub climb_mps: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Fallbacks {
+    pub hold: FallbackCommand,
+    pub r#override: FallbackCommand,
+    pub shutdown: FallbackCommand,
+    pub loiter: FallbackCommand,
+    pub stall: FallbackCommand,
+}
+
+pub fn validate_fallbacks(fallbacks: &Fallbacks, flow: &FlowSpec) -> Result<()> {
+    let max_rates_dps = [
+        flow.max_roll_rate_dps,
+        flow.max_pitch_rate_dps,
+        flow.max_yaw_rate_dps,
+    ];
+    for (name, command) in [
+        ("hold", &fallbacks.hold),
+        ("override", &fallbacks.r#override),
+        ("shutdown", &fallbacks.shutdown),
+        ("loiter", &fallbacks.loiter),
+        ("stall", &fallbacks.stall),
+    ] {
+        for (axis, (rate, max)) in command.rates_dps.iter().zip(max_rates_dps).enumerate() {
+            if !rate.is_finite() || rate.abs() > max {
+                bail!("fallback {name} rates_dps[{axis}] ({rate}) exceeds the flow limit")
+            }
+        }
+        if !command.climb_mps.is_finite() || command.climb_mps.abs() > flow.max_climb_rate_mps {
+            bail!(
+                "fallback {name} climb_mps ({}) exceeds the flow limit",
+                command.climb_mps
+            )
+        }
+    }
+    Ok(())
+}
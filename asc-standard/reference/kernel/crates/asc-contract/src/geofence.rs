@@ -0,0 +1,70 @@
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::StateSpec;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GeofenceSpec {
+    pub frame: String,
+    pub inclusion: FenceZoneSpec,
+    #[serde(default)]
+    pub exclusions: Vec<FenceZoneSpec>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FenceZoneSpec {
+    pub id: String,
+    pub shape: FenceShapeSpec,
+    pub floor_m: f64,
+    pub ceiling_m: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case", deny_unknown_fields)]
+pub enum FenceShapeSpec {
+    Polygon { vertices_m: Vec<[f64; 2]> },
+    Cylinder { center_m: [f64; 2], radius_m: f64 },
+}
+
+// Zone floors and ceilings are altitudes, positive up, while the state
+// position bounds are NED; the fence must lie within the altitudes the bounds
+// admit.
+pub fn validate_geofence(fence: &GeofenceSpec, state: &StateSpec) -> Result<()> {
+    if fence.frame != state.frame {
+        bail!(
+            "geofence frame {} does not match state frame {}",
+            fence.frame,
+            state.frame
+        )
+    }
+    let lowest_m = -state.position_bounds_m.max[2];
+    let highest_m = -state.position_bounds_m.min[2];
+    for zone in std::iter::once(&fence.inclusion).chain(&fence.exclusions) {
+        if zone.floor_m >= zone.ceiling_m {
+            bail!("geofence zone {} floor_m must be < ceiling_m", zone.id)
+        }
+        if zone.floor_m < lowest_m || zone.ceiling_m > highest_m {
+            bail!(
+                "geofence zone {} altitudes [{}, {}] leave the position bounds' altitudes [{lowest_m}, {highest_m}]",
+                zone.id,
+                zone.floor_m,
+                zone.ceiling_m
+            )
+        }
+        match &zone.shape {
+            FenceShapeSpec::Polygon { vertices_m } if vertices_m.len() < 3 => {
+                bail!(
+                    "geofence zone {} polygon needs at least 3 vertices",
+                    zone.id
+                )
+            }
+            FenceShapeSpec::Cylinder { radius_m, .. } if *radius_m <= 0.0 => {
+                bail!("geofence zone {} radius_m must be > 0", zone.id)
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
@@ -1,9 +1,30 @@
-use anyhow::{Context, Result};
-use serde::Deserialize;
+mod capability;
+mod checks;
+mod fallback;
+mod geofence;
+mod overlay;
+mod phase;
+
+use anyhow::{bail, Context, Result};
+use asc_types::Verdict;
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
+use std::collections::BTreeSet;
 use std::fs;
 use std::path::Path;
 
+pub use capability::{validate_capabilities, validate_transition};
+pub use checks::{validate_checks, ProgramCheckSpec, ProgramChecks};
+pub use fallback::{validate_fallbacks, FallbackCommand, Fallbacks};
+pub use geofence::{validate_geofence, FenceShapeSpec, FenceZoneSpec, GeofenceSpec};
+pub use overlay::{
+    apply_overrides, EnergyOverride, FallbacksOverride, FlowOverride, GuaranteesOverride,
+    InvariantsOverride, ProfileOverrides, Sections, StateOverride,
+};
+pub use phase::{
+    validate_phases, PhaseFlowOverride, PhaseInvariantsOverride, PhaseOverride, Phases,
+};
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct TupleSpec {
@@ -12,32 +33,35 @@ pub struct TupleSpec {
     pub severities: Vec<String>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct StateSpec {
     pub frame: String,
     pub position_bounds_m: PositionBounds,
+    pub position_bounds_verdict: Verdict,
     pub attitude_limit_deg: f64,
     pub max_speed_mps: f64,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct PositionBounds {
     pub min: [f64; 3],
     pub max: [f64; 3],
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct FlowSpec {
     pub max_roll_rate_dps: f64,
     pub max_pitch_rate_dps: f64,
     pub max_yaw_rate_dps: f64,
     pub max_climb_rate_mps: f64,
+    pub max_rate_step_dps: f64,
+    pub max_climb_step_mps: f64,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct EnergySpec {
     pub min_soc_percent: f64,
@@ -45,27 +69,103 @@ pub struct EnergySpec {
     pub max_power_w: f64,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct Guarantees {
     pub max_input_age_ms: u64,
     pub max_tick_interval_ms: u64,
     pub deadline_ms: u64,
+    #[serde(default)]
+    pub formulas: Vec<StlFormulaSpec>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StlFormulaSpec {
+    pub id: String,
+    pub formula: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct Invariants {
     pub min_altitude_m: f64,
     pub max_bank_deg: f64,
+    pub barrier_gain_per_s: f64,
     pub require_geofence: bool,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Deescalation {
+    pub clean_ticks: u32,
+    pub min_dwell_ms: u64,
+    pub exit_bands: ExitBands,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ExitBands {
+    pub soc_percent: f64,
+    pub speed_mps: f64,
+}
+
+// Exit thresholds must stay reachable: an overspeed has to clear above zero
+// speed and a SOC floor violation at or below a full battery.
+pub fn validate_deescalation(
+    deescalation: &Deescalation,
+    state: &StateSpec,
+    energy: &EnergySpec,
+) -> Result<()> {
+    let bands = &deescalation.exit_bands;
+    if [bands.soc_percent, bands.speed_mps]
+        .iter()
+        .any(|band| !band.is_finite() || *band < 0.0)
+    {
+        bail!("deescalation exit bands must be finite and >= 0")
+    }
+    if bands.speed_mps >= state.max_speed_mps {
+        bail!("deescalation.exit_bands.speed_mps must be < state.max_speed_mps")
+    }
+    if energy.min_soc_percent + bands.soc_percent > 100.0 {
+        bail!("deescalation.exit_bands.soc_percent pushes the SOC exit threshold above 100")
+    }
+    Ok(())
+}
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct InterlockGate {
     pub armed_required: bool,
     pub fault_latched_shutdown: bool,
+    pub reset_authorities: Vec<String>,
+}
+
+// Tilt is never less than bank, so a soft attitude limit at or inside the bank
+// invariant holds the vehicle before the invariant shuts it down.
+pub fn validate_attitude(state: &StateSpec, invariants: &Invariants) -> Result<()> {
+    if state.attitude_limit_deg > invariants.max_bank_deg {
+        bail!(
+            "state.attitude_limit_deg ({}) must be <= invariants.max_bank_deg ({})",
+            state.attitude_limit_deg,
+            invariants.max_bank_deg
+        )
+    }
+    Ok(())
+}
+
+// A latching gate needs an authority that can reset it.
+pub fn validate_interlock(interlock: &InterlockGate) -> Result<()> {
+    let mut authorities = BTreeSet::new();
+    for authority in &interlock.reset_authorities {
+        if authority.is_empty() || !authorities.insert(authority.as_str()) {
+            bail!("reset authority {authority:?} is empty or duplicated")
+        }
+    }
+    if interlock.fault_latched_shutdown && authorities.is_empty() {
+        bail!("fault_latched_shutdown requires at least one reset authority")
+    }
+    Ok(())
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -74,6 +174,30 @@ pub struct Profile {
     pub name: String,
     pub timing: ProfileTiming,
     pub capabilities: ProfileCapabilities,
+    #[serde(default)]
+    pub geofence: Option<String>,
+    #[serde(default)]
+    pub overrides: ProfileOverrides,
+    #[serde(default)]
+    pub transition: Option<TransitionSpec>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TransitionSpec {
+    pub hover_airspeed_mps: f64,
+    pub hover: ModeLimitsSpec,
+    pub forward: ModeLimitsSpec,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ModeLimitsSpec {
+    pub max_bank_deg: f64,
+    pub max_roll_rate_dps: f64,
+    pub max_pitch_rate_dps: f64,
+    pub max_yaw_rate_dps: f64,
+    pub max_climb_rate_mps: f64,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -89,6 +213,10 @@ pub struct ProfileCapabilities {
     pub vtol: bool,
     pub fixed_wing: bool,
     pub max_payload_kg: f64,
+    #[serde(default)]
+    pub min_airspeed_mps: Option<f64>,
+    #[serde(default)]
+    pub max_hover_descent_mps: Option<f64>,
 }
 
 #[derive(Debug, Clone)]
@@ -100,7 +228,12 @@ pub struct ContractBundle {
     pub guarantees: Guarantees,
     pub invariants: Invariants,
     pub interlock: InterlockGate,
+    pub deescalation: Deescalation,
+    pub fallbacks: Fallbacks,
+    pub phases: Phases,
+    pub checks: ProgramChecks,
     pub profile: Profile,
+    pub geofence: Option<GeofenceSpec>,
     pub fingerprint: String,
 }
 
@@ -112,16 +245,75 @@ pub fn load_contract(repo_root: &Path, profile_name: &str) -> Result<ContractBun
     let guarantees_raw = read(repo_root.join("spec/asc/guarantees-stl.yaml"))?;
     let inv_raw = read(repo_root.join("spec/asc/invariants-rcbf.yaml"))?;
     let interlock_raw = read(repo_root.join("spec/asc/interlock-gate.yaml"))?;
+    let deescalation_raw = read(repo_root.join("spec/asc/deescalation.yaml"))?;
+    let fallbacks_raw = read(repo_root.join("spec/asc/fallbacks.yaml"))?;
+    let phases_raw = read(repo_root.join("spec/asc/phases.yaml"))?;
+    let checks_raw = read(repo_root.join("spec/asc/checks.yaml"))?;
     let profile_raw = read(repo_root.join(format!("spec/profiles/{profile_name}.yaml")))?;
 
     let tuple: TupleSpec = serde_yaml::from_str(&tuple_raw)?;
-    let state: StateSpec = serde_yaml::from_str(&state_raw)?;
-    let flow: FlowSpec = serde_yaml::from_str(&flow_raw)?;
-    let energy: EnergySpec = serde_yaml::from_str(&energy_raw)?;
-    let guarantees: Guarantees = serde_yaml::from_str(&guarantees_raw)?;
-    let invariants: Invariants = serde_yaml::from_str(&inv_raw)?;
+    let mut state: StateSpec = serde_yaml::from_str(&state_raw)?;
+    let mut flow: FlowSpec = serde_yaml::from_str(&flow_raw)?;
+    let mut energy: EnergySpec = serde_yaml::from_str(&energy_raw)?;
+    let mut guarantees: Guarantees = serde_yaml::from_str(&guarantees_raw)?;
+    let mut invariants: Invariants = serde_yaml::from_str(&inv_raw)?;
     let interlock: InterlockGate = serde_yaml::from_str(&interlock_raw)?;
+    let deescalation: Deescalation = serde_yaml::from_str(&deescalation_raw)?;
+    let mut fallbacks: Fallbacks = serde_yaml::from_str(&fallbacks_raw)?;
+    let phases: Phases = serde_yaml::from_str(&phases_raw)?;
+    let checks: ProgramChecks = serde_yaml::from_str(&checks_raw)?;
     let profile: Profile = serde_yaml::from_str(&profile_raw)?;
+    let geofence_raw = profile
+        .geofence
+        .as_ref()
+        .map(|name| read(repo_root.join(format!("spec/geofences/{name}.yaml"))))
+        .transpose()?;
+    let geofence: Option<GeofenceSpec> = geofence_raw
+        .as_deref()
+        .map(serde_yaml::from_str)
+        .transpose()?;
+
+    apply_overrides(
+        &profile.name,
+        &profile.overrides,
+        Sections {
+            state: &mut state,
+            flow: &mut flow,
+            energy: &mut energy,
+            guarantees: &mut guarantees,
+            invariants: &mut invariants,
+            fallbacks: &mut fallbacks,
+        },
+    )?;
+    let merged_raw =
+        serde_yaml::to_string(&(&state, &flow, &energy, &guarantees, &invariants, &fallbacks))?;
+
+    validate_fallbacks(&fallbacks, &flow)?;
+    validate_capabilities(&profile.capabilities, &fallbacks)?;
+    validate_transition(&profile)?;
+    validate_phases(&phases)?;
+    validate_attitude(&state, &invariants)?;
+    validate_deescalation(&deescalation, &state, &energy)?;
+    validate_interlock(&interlock)?;
+    validate_checks(&checks)?;
+    let mut formula_ids = BTreeSet::new();
+    for formula in &guarantees.formulas {
+        if formula.id.is_empty() || !formula_ids.insert(formula.id.as_str()) {
+            bail!(
+                "guarantee formula id {:?} is empty or duplicated",
+                formula.id
+            )
+        }
+    }
+
+    match &geofence {
+        Some(fence) => validate_geofence(fence, &state)?,
+        None if invariants.require_geofence => bail!(
+            "profile {} requires a geofence but none is configured",
+            profile.name
+        ),
+        None => {}
+    }
 
     let mut hasher = Sha256::new();
     for raw in [
@@ -132,10 +324,18 @@ pub fn load_contract(repo_root: &Path, profile_name: &str) -> Result<ContractBun
         guarantees_raw,
         inv_raw,
         interlock_raw,
+        deescalation_raw,
+        fallbacks_raw,
+        phases_raw,
+        checks_raw,
         profile_raw,
+        merged_raw,
     ] {
         hasher.update(normalize(&raw));
     }
+    if let Some(raw) = &geofence_raw {
+        hasher.update(normalize(raw));
+    }
 
     Ok(ContractBundle {
         tuple,
@@ -145,7 +345,12 @@ pub fn load_contract(repo_root: &Path, profile_name: &str) -> Result<ContractBun
         guarantees,
         invariants,
         interlock,
+        deescalation,
+        fallbacks,
+        phases,
+        checks,
         profile,
+        geofence,
         fingerprint: hex::encode(hasher.finalize()),
     })
 }
@@ -164,20 +369,340 @@ fn normalize(input: &str) -> String {
         .join("\n")
 }
 
+#[cfg(test)]
+#[path = "../../asc-conformance-kernel/tests/common/repo.rs"]
+mod test_repo;
+
 #[cfg(test)]
 mod tests {
-    use super::load_contract;
-    use std::path::PathBuf;
+    use super::*;
+    use crate::test_repo::{repo_root, scratch_repo};
 
     #[test]
     fn loads_full_contract_bundle() {
-        let repo_root = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
-            .join("../../../../")
-            .canonicalize()
-            .expect("canonical repo root");
-        let bundle = load_contract(&repo_root, "uas-small").expect("load contract");
+        let bundle = load_contract(&repo_root(), "uas-small").expect("load contract");
         assert_eq!(bundle.profile.name, "uas-small");
         assert_eq!(bundle.state.frame, "NED");
         assert!(!bundle.fingerprint.is_empty());
     }
+
+    #[test]
+    fn profile_overrides_are_merged_and_fingerprinted() {
+        let base = load_contract(&repo_root(), "uas-small").expect("load uas-small");
+        let vtol = load_contract(&repo_root(), "hybrid-vtol").expect("load hybrid-vtol");
+
+        assert_eq!(base.flow.max_climb_rate_mps, 8.0);
+        assert_eq!(vtol.flow.max_climb_rate_mps, 6.0);
+        assert_eq!(vtol.invariants.min_altitude_m, 8.0);
+        assert_eq!(vtol.invariants.max_bank_deg, 45.0);
+        assert_eq!(vtol.flow.max_roll_rate_dps, base.flow.max_roll_rate_dps);
+        assert_ne!(vtol.fingerprint, base.fingerprint);
+    }
+
+    #[test]
+    fn class_a_relaxation_is_scoped_to_listed_fields() {
+        let bundle = load_contract(&repo_root(), "uas-small").expect("load contract");
+        let (mut state, mut flow, mut energy, mut guarantees, mut invariants, mut fallbacks) = (
+            bundle.state,
+            bundle.flow,
+            bundle.energy,
+            bundle.guarantees,
+            bundle.invariants,
+            bundle.fallbacks,
+        );
+        let mut overrides: ProfileOverrides = serde_yaml::from_str(
+            "flow: { max_climb_rate_mps: 12.0 }\ninvariants: { min_altitude_m: 2.0 }\n",
+        )
+        .expect("parse overrides");
+
+        let err = apply_overrides(
+            "test",
+            &overrides,
+            Sections {
+                state: &mut state,
+                flow: &mut flow,
+                energy: &mut energy,
+                guarantees: &mut guarantees,
+                invariants: &mut invariants,
+                fallbacks: &mut fallbacks,
+            },
+        )
+        .expect_err("relaxation must be rejected");
+        assert!(err.to_string().contains("flow.max_climb_rate_mps"));
+
+        overrides.class_a_relaxation = vec!["flow.max_climb_rate_mps".into()];
+        let err = apply_overrides(
+            "test",
+            &overrides,
+            Sections {
+                state: &mut state,
+                flow: &mut flow,
+                energy: &mut energy,
+                guarantees: &mut guarantees,
+                invariants: &mut invariants,
+                fallbacks: &mut fallbacks,
+            },
+        )
+        .expect_err("unlisted relaxation must be rejected");
+        assert!(err.to_string().contains("invariants.min_altitude_m"));
+
+        overrides.class_a_relaxation = vec!["flow.max_climb_rate_mps".into(), "invariants".into()];
+        apply_overrides(
+            "test",
+            &overrides,
+            Sections {
+                state: &mut state,
+                flow: &mut flow,
+                energy: &mut energy,
+                guarantees: &mut guarantees,
+                invariants: &mut invariants,
+                fallbacks: &mut fallbacks,
+            },
+        )
+        .expect("listed relaxation");
+        assert_eq!(flow.max_climb_rate_mps, 12.0);
+        assert_eq!(invariants.min_altitude_m, 2.0);
+
+        let overrides: ProfileOverrides =
+            serde_yaml::from_str("flow: { max_rate_step_dps: 90.0 }\n").expect("parse overrides");
+        let err = apply_overrides(
+            "test",
+            &overrides,
+            Sections {
+                state: &mut state,
+                flow: &mut flow,
+                energy: &mut energy,
+                guarantees: &mut guarantees,
+                invariants: &mut invariants,
+                fallbacks: &mut fallbacks,
+            },
+        )
+        .expect_err("slew relaxation must be rejected");
+        assert!(err.to_string().contains("flow.max_rate_step_dps"));
+
+        for (yaml, field) in [
+            (
+                "fallbacks: { override: { rates_dps: [0.0, 0.0, 6.0], climb_mps: -1.0 } }\n",
+                "fallbacks.override",
+            ),
+            (
+                "class_a_relaxation: [flow.max_roll_rate]\nflow: { max_roll_rate_dps: 1.0 }\n",
+                "flow.max_roll_rate",
+            ),
+            (
+                "class_a_relaxation: [energy]\nenergy: { min_soc_percent: .nan }\n",
+                "energy.min_soc_percent",
+            ),
+            (
+                "class_a_relaxation: [fallbacks.hold]\nfallbacks: { hold: { rates_dps: [0.0, 0.0, 0.0], climb_mps: .inf } }\n",
+                "fallbacks.hold",
+            ),
+        ] {
+            let overrides: ProfileOverrides = serde_yaml::from_str(yaml).expect("parse overrides");
+            let err = apply_overrides(
+                "test",
+                &overrides,
+                Sections {
+                    state: &mut state,
+                    flow: &mut flow,
+                    energy: &mut energy,
+                    guarantees: &mut guarantees,
+                    invariants: &mut invariants,
+                    fallbacks: &mut fallbacks,
+                },
+            )
+            .expect_err("override must be rejected");
+            assert!(err.to_string().contains(field), "{err}");
+        }
+    }
+
+    #[test]
+    fn required_geofence_must_be_configured() {
+        let scratch = scratch_repo("contract");
+        let profile_path = scratch.join("spec/profiles/uas-small.yaml");
+        let profile = fs::read_to_string(&profile_path).expect("read profile");
+        fs::write(
+            &profile_path,
+            profile.replace("geofence: reference-range\n", ""),
+        )
+        .expect("write profile");
+
+        let err = load_contract(&scratch, "uas-small").expect_err("missing geofence rejected");
+        assert!(err.to_string().contains("requires a geofence"));
+        fs::remove_dir_all(&scratch).expect("remove scratch");
+    }
+
+    #[test]
+    fn geofence_altitudes_must_lie_within_position_bounds() {
+        let scratch = scratch_repo("fence");
+        // A fence written with NED down components instead of altitudes.
+        let fence_path = scratch.join("spec/geofences/reference-range.yaml");
+        let fence = fs::read_to_string(&fence_path).expect("read fence");
+        fs::write(
+            &fence_path,
+            fence
+                .replace(
+                    "floor_m: 0.0\n  ceiling_m: 100.0",
+                    "floor_m: -100.0\n  ceiling_m: 0.0",
+                )
+                .replace(
+                    "floor_m: 0.0\n    ceiling_m: 60.0",
+                    "floor_m: -1100.0\n    ceiling_m: -60.0",
+                ),
+        )
+        .expect("write fence");
+
+        let err = load_contract(&scratch, "uas-small").expect_err("NED fence rejected");
+        assert!(err.to_string().contains("mast-north"), "{err}");
+        fs::remove_dir_all(&scratch).expect("remove scratch");
+    }
+
+    #[test]
+    fn profile_fallbacks_are_merged_and_checked_against_flow() {
+        let base = load_contract(&repo_root(), "uas-small").expect("load uas-small");
+        let fixed_wing = load_contract(&repo_root(), "fixed-wing").expect("load fixed-wing");
+        assert_eq!(base.fallbacks.r#override.climb_mps, -1.0);
+        assert_eq!(fixed_wing.fallbacks.r#override.climb_mps, 1.0);
+        assert_eq!(fixed_wing.fallbacks.shutdown, base.fallbacks.shutdown);
+
+        let scratch = scratch_repo("contract-fallbacks");
+        let fallbacks_path = scratch.join("spec/asc/fallbacks.yaml");
+        let fallbacks = fs::read_to_string(&fallbacks_path).expect("read fallbacks");
+        fs::write(
+            &fallbacks_path,
+            fallbacks.replace("climb_mps: -1.0", "climb_mps: -20.0"),
+        )
+        .expect("write fallbacks");
+
+        let err = load_contract(&scratch, "uas-small").expect_err("fallback outside flow rejected");
+        assert!(err.to_string().contains("fallback override climb_mps"));
+        fs::remove_dir_all(&scratch).expect("remove scratch");
+    }
+
+    #[test]
+    fn fixed_wing_profiles_require_stall_speed_and_loiter() {
+        let fixed_wing = load_contract(&repo_root(), "fixed-wing").expect("load fixed-wing");
+        assert_eq!(fixed_wing.profile.capabilities.min_airspeed_mps, Some(14.0));
+
+        let scratch = scratch_repo("contract-capabilities");
+        let profile_path = scratch.join("spec/profiles/fixed-wing.yaml");
+        let profile = fs::read_to_string(&profile_path).expect("read profile");
+        fs::write(
+            &profile_path,
+            format!(
+                "{profile}    loiter:\n      rates_dps: [0.0, 0.0, 0.0]\n      climb_mps: 0.0\n"
+            ),
+        )
+        .expect("write profile");
+        let err = load_contract(&scratch, "fixed-wing").expect_err("zero-rate loiter rejected");
+        assert!(err.to_string().contains("non-zero turn rate"));
+
+        fs::write(
+            &profile_path,
+            profile.replace("  min_airspeed_mps: 14.0\n", ""),
+        )
+        .expect("write profile");
+        let err = load_contract(&scratch, "fixed-wing").expect_err("missing stall speed rejected");
+        assert!(err.to_string().contains("min_airspeed_mps"));
+        fs::remove_dir_all(&scratch).expect("remove scratch");
+    }
+
+    #[test]
+    fn hybrid_profiles_require_a_valid_transition() {
+        let hybrid = load_contract(&repo_root(), "hybrid-vtol").expect("load hybrid-vtol");
+        let transition = hybrid.profile.transition.expect("hybrid transition");
+        assert_eq!(transition.hover_airspeed_mps, 6.0);
+
+        let scratch = scratch_repo("contract-transition");
+        let profile_path = scratch.join("spec/profiles/hybrid-vtol.yaml");
+        let profile = fs::read_to_string(&profile_path).expect("read profile");
+        fs::write(
+            &profile_path,
+            profile.replace("hover_airspeed_mps: 6.0", "hover_airspeed_mps: 20.0"),
+        )
+        .expect("write profile");
+        let err = load_contract(&scratch, "hybrid-vtol").expect_err("hover above stall rejected");
+        assert!(err.to_string().contains("hover_airspeed_mps"));
+
+        let (head, _) = profile
+            .split_once("transition:")
+            .expect("transition section");
+        fs::write(&profile_path, head).expect("write profile");
+        let err = load_contract(&scratch, "hybrid-vtol").expect_err("missing transition rejected");
+        assert!(err.to_string().contains("transition"));
+        fs::remove_dir_all(&scratch).expect("remove scratch");
+    }
+
+    #[test]
+    fn program_checks_are_validated_and_fingerprinted() {
+        let base = load_contract(&repo_root(), "uas-small").expect("load uas-small");
+        assert!(base.checks.checks.is_empty());
+
+        let scratch = scratch_repo("contract-checks");
+        let checks_path = scratch.join("spec/asc/checks.yaml");
+        let declared = "checks:\n  - id: link-loss\n    reason: InputStale\n    severity: Critical\n    verdict: Hold\n";
+        fs::write(&checks_path, declared).expect("write checks");
+        let bundle = load_contract(&scratch, "uas-small").expect("declared check accepted");
+        assert_eq!(
+            bundle.checks.checks[0].reason,
+            asc_types::ReasonCode::InputStale
+        );
+        assert_ne!(bundle.fingerprint, base.fingerprint);
+
+        fs::write(&checks_path, declared.replace("Hold", "Allow")).expect("write checks");
+        let err = load_contract(&scratch, "uas-small").expect_err("Allow verdict rejected");
+        assert!(err.to_string().contains("above Allow"));
+
+        fs::write(
+            &checks_path,
+            format!("{declared}{}", declared.trim_start_matches("checks:\n")),
+        )
+        .expect("write checks");
+        let err = load_contract(&scratch, "uas-small").expect_err("duplicate id rejected");
+        assert!(err.to_string().contains("duplicated"));
+        fs::remove_dir_all(&scratch).expect("remove scratch");
+    }
+
+    #[test]
+    fn attitude_limit_must_not_exceed_the_bank_invariant() {
+        let vtol = load_contract(&repo_root(), "hybrid-vtol").expect("load hybrid-vtol");
+        assert!(vtol.state.attitude_limit_deg <= vtol.invariants.max_bank_deg);
+
+        let scratch = scratch_repo("contract-attitude");
+        let state_path = scratch.join("spec/asc/state-se3.yaml");
+        let state = fs::read_to_string(&state_path).expect("read state");
+        fs::write(
+            &state_path,
+            state.replace("attitude_limit_deg: 55.0", "attitude_limit_deg: 80.0"),
+        )
+        .expect("write state");
+        let err = load_contract(&scratch, "uas-small").expect_err("loose soft limit rejected");
+        assert!(err.to_string().contains("attitude_limit_deg"), "{err}");
+        fs::remove_dir_all(&scratch).expect("remove scratch");
+    }
+
+    #[test]
+    fn deescalation_exit_bands_are_validated() {
+        let scratch = scratch_repo("contract-exit-bands");
+        let path = scratch.join("spec/asc/deescalation.yaml");
+        let deescalation = fs::read_to_string(&path).expect("read deescalation");
+        for (band, expected) in [
+            ("soc_percent: -1.0", "finite and >= 0"),
+            ("soc_percent: .nan", "finite and >= 0"),
+            ("soc_percent: 90.0", "above 100"),
+        ] {
+            fs::write(&path, deescalation.replace("soc_percent: 2.0", band))
+                .expect("write deescalation");
+            let err = load_contract(&scratch, "uas-small").expect_err("exit band rejected");
+            assert!(err.to_string().contains(expected), "{err}");
+        }
+        fs::write(
+            &path,
+            deescalation.replace("speed_mps: 2.0", "speed_mps: 500.0"),
+        )
+        .expect("write deescalation");
+        let err = load_contract(&scratch, "uas-small").expect_err("speed band rejected");
+        assert!(err.to_string().contains("exit_bands.speed_mps"), "{err}");
+        fs::remove_dir_all(&scratch).expect("remove scratch");
+    }
 }
@@ -0,0 +1,411 @@
+use anyhow::{bail, Result};
+use asc_types::Verdict;
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+
+use crate::{
+    EnergySpec, FallbackCommand, Fallbacks, FlowSpec, Guarantees, Invariants, PositionBounds,
+    StateSpec, StlFormulaSpec,
+};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProfileOverrides {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub class_a_relaxation: Vec<String>,
+    #[serde(default)]
+    pub state: Option<StateOverride>,
+    #[serde(default)]
+    pub flow: Option<FlowOverride>,
+    #[serde(default)]
+    pub energy: Option<EnergyOverride>,
+    #[serde(default)]
+    pub guarantees: Option<GuaranteesOverride>,
+    #[serde(default)]
+    pub invariants: Option<InvariantsOverride>,
+    #[serde(default)]
+    pub fallbacks: Option<FallbacksOverride>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StateOverride {
+    pub position_bounds_m: Option<PositionBounds>,
+    pub position_bounds_verdict: Option<Verdict>,
+    pub attitude_limit_deg: Option<f64>,
+    pub max_speed_mps: Option<f64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FlowOverride {
+    pub max_roll_rate_dps: Option<f64>,
+    pub max_pitch_rate_dps: Option<f64>,
+    pub max_yaw_rate_dps: Option<f64>,
+    pub max_climb_rate_mps: Option<f64>,
+    pub max_rate_step_dps: Option<f64>,
+    pub max_climb_step_mps: Option<f64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EnergyOverride {
+    pub min_soc_percent: Option<f64>,
+    pub reserve_endurance_s: Option<f64>,
+    pub max_power_w: Option<f64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GuaranteesOverride {
+    pub max_input_age_ms: Option<u64>,
+    pub max_tick_interval_ms: Option<u64>,
+    pub deadline_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub formulas: Vec<StlFormulaSpec>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct InvariantsOverride {
+    pub min_altitude_m: Option<f64>,
+    pub max_bank_deg: Option<f64>,
+    pub barrier_gain_per_s: Option<f64>,
+    pub require_geofence: Option<bool>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FallbacksOverride {
+    pub hold: Option<FallbackCommand>,
+    pub r#override: Option<FallbackCommand>,
+    pub shutdown: Option<FallbackCommand>,
+    pub loiter: Option<FallbackCommand>,
+    pub stall: Option<FallbackCommand>,
+}
+
+pub struct Sections<'a> {
+    pub state: &'a mut StateSpec,
+    pub flow: &'a mut FlowSpec,
+    pub energy: &'a mut EnergySpec,
+    pub guarantees: &'a mut Guarantees,
+    pub invariants: &'a mut Invariants,
+    pub fallbacks: &'a mut Fallbacks,
+}
+
+// Every overridable field bounds what the vehicle is commanded to do and is
+// treated as Class A: an overlay may only tighten it unless the profile lists
+// the field, or a section containing it, under `class_a_relaxation`. Every
+// listed entry must name a field the overlay sets. A fallback command tightens
+// when no axis moves faster and it climbs or descends no faster than the base
+// command. Non-finite overrides are always rejected. Guarantee formulas are
+// appended to the base list: a monitored formula can only add violations.
+pub fn apply_overrides(
+    profile_name: &str,
+    overrides: &ProfileOverrides,
+    sections: Sections<'_>,
+) -> Result<()> {
+    let mut merge = Merge {
+        profile: profile_name,
+        relax: &overrides.class_a_relaxation,
+        used: vec![false; overrides.class_a_relaxation.len()],
+    };
+
+    if let Some(o) = &overrides.state {
+        if let Some(bounds) = &o.position_bounds_m {
+            for axis in 0..3 {
+                merge.class_a(
+                    &format!("state.position_bounds_m.min[{axis}]"),
+                    &mut sections.state.position_bounds_m.min[axis],
+                    Some(bounds.min[axis]),
+                    Tighter::Higher,
+                )?;
+                merge.class_a(
+                    &format!("state.position_bounds_m.max[{axis}]"),
+                    &mut sections.state.position_bounds_m.max[axis],
+                    Some(bounds.max[axis]),
+                    Tighter::Lower,
+                )?;
+            }
+        }
+        merge.verdict(
+            "state.position_bounds_verdict",
+            &mut sections.state.position_bounds_verdict,
+            o.position_bounds_verdict,
+        )?;
+        merge.class_a(
+            "state.attitude_limit_deg",
+            &mut sections.state.attitude_limit_deg,
+            o.attitude_limit_deg,
+            Tighter::Lower,
+        )?;
+        merge.class_a(
+            "state.max_speed_mps",
+            &mut sections.state.max_speed_mps,
+            o.max_speed_mps,
+            Tighter::Lower,
+        )?;
+    }
+    if let Some(o) = &overrides.flow {
+        merge.class_a(
+            "flow.max_roll_rate_dps",
+            &mut sections.flow.max_roll_rate_dps,
+            o.max_roll_rate_dps,
+            Tighter::Lower,
+        )?;
+        merge.class_a(
+            "flow.max_pitch_rate_dps",
+            &mut sections.flow.max_pitch_rate_dps,
+            o.max_pitch_rate_dps,
+            Tighter::Lower,
+        )?;
+        merge.class_a(
+            "flow.max_yaw_rate_dps",
+            &mut sections.flow.max_yaw_rate_dps,
+            o.max_yaw_rate_dps,
+            Tighter::Lower,
+        )?;
+        merge.class_a(
+            "flow.max_climb_rate_mps",
+            &mut sections.flow.max_climb_rate_mps,
+            o.max_climb_rate_mps,
+            Tighter::Lower,
+        )?;
+        merge.class_a(
+            "flow.max_rate_step_dps",
+            &mut sections.flow.max_rate_step_dps,
+            o.max_rate_step_dps,
+            Tighter::Lower,
+        )?;
+        merge.class_a(
+            "flow.max_climb_step_mps",
+            &mut sections.flow.max_climb_step_mps,
+            o.max_climb_step_mps,
+            Tighter::Lower,
+        )?;
+    }
+    if let Some(o) = &overrides.energy {
+        merge.class_a(
+            "energy.min_soc_percent",
+            &mut sections.energy.min_soc_percent,
+            o.min_soc_percent,
+            Tighter::Higher,
+        )?;
+        merge.class_a(
+            "energy.reserve_endurance_s",
+            &mut sections.energy.reserve_endurance_s,
+            o.reserve_endurance_s,
+            Tighter::Higher,
+        )?;
+        merge.class_a(
+            "energy.max_power_w",
+            &mut sections.energy.max_power_w,
+            o.max_power_w,
+            Tighter::Lower,
+        )?;
+    }
+    if let Some(o) = &overrides.guarantees {
+        merge.class_a(
+            "guarantees.max_input_age_ms",
+            &mut sections.guarantees.max_input_age_ms,
+            o.max_input_age_ms,
+            Tighter::Lower,
+        )?;
+        merge.class_a(
+            "guarantees.max_tick_interval_ms",
+            &mut sections.guarantees.max_tick_interval_ms,
+            o.max_tick_interval_ms,
+            Tighter::Lower,
+        )?;
+        merge.class_a(
+            "guarantees.deadline_ms",
+            &mut sections.guarantees.deadline_ms,
+            o.deadline_ms,
+            Tighter::Lower,
+        )?;
+        sections
+            .guarantees
+            .formulas
+            .extend(o.formulas.iter().cloned());
+    }
+    if let Some(o) = &overrides.invariants {
+        merge.class_a(
+            "invariants.min_altitude_m",
+            &mut sections.invariants.min_altitude_m,
+            o.min_altitude_m,
+            Tighter::Higher,
+        )?;
+        merge.class_a(
+            "invariants.max_bank_deg",
+            &mut sections.invariants.max_bank_deg,
+            o.max_bank_deg,
+            Tighter::Lower,
+        )?;
+        merge.class_a(
+            "invariants.barrier_gain_per_s",
+            &mut sections.invariants.barrier_gain_per_s,
+            o.barrier_gain_per_s,
+            Tighter::Lower,
+        )?;
+        merge.class_a(
+            "invariants.require_geofence",
+            &mut sections.invariants.require_geofence,
+            o.require_geofence,
+            Tighter::Higher,
+        )?;
+    }
+
+    if let Some(o) = &overrides.fallbacks {
+        merge.command("fallbacks.hold", &mut sections.fallbacks.hold, o.hold)?;
+        merge.command(
+            "fallbacks.override",
+            &mut sections.fallbacks.r#override,
+            o.r#override,
+        )?;
+        merge.command(
+            "fallbacks.shutdown",
+            &mut sections.fallbacks.shutdown,
+            o.shutdown,
+        )?;
+        merge.command("fallbacks.loiter", &mut sections.fallbacks.loiter, o.loiter)?;
+        merge.command("fallbacks.stall", &mut sections.fallbacks.stall, o.stall)?;
+    }
+
+    if let Some((entry, _)) = merge
+        .relax
+        .iter()
+        .zip(&merge.used)
+        .find(|(_, used)| !**used)
+    {
+        bail!("profile {profile_name} lists {entry} under class_a_relaxation but overrides no field in it")
+    }
+    Ok(())
+}
+
+trait Limit: Copy + PartialOrd + Debug {
+    fn is_finite(&self) -> bool {
+        true
+    }
+}
+
+impl Limit for f64 {
+    fn is_finite(&self) -> bool {
+        f64::is_finite(*self)
+    }
+}
+
+impl Limit for u64 {}
+
+impl Limit for bool {}
+
+#[derive(Clone, Copy)]
+enum Tighter {
+    Lower,
+    Higher,
+}
+
+struct Merge<'a> {
+    profile: &'a str,
+    relax: &'a [String],
+    used: Vec<bool>,
+}
+
+impl Merge<'_> {
+    fn class_a<T: Limit>(
+        &mut self,
+        field: &str,
+        base: &mut T,
+        value: Option<T>,
+        tighter: Tighter,
+    ) -> Result<()> {
+        let Some(value) = value else {
+            return Ok(());
+        };
+        if !value.is_finite() {
+            bail!(
+                "profile {} sets {field} to a non-finite value ({value:?})",
+                self.profile
+            )
+        }
+        let relaxes = match tighter {
+            Tighter::Lower => value > *base,
+            Tighter::Higher => value < *base,
+        };
+        self.apply(field, base, value, relaxes)
+    }
+
+    fn command(
+        &mut self,
+        field: &str,
+        base: &mut FallbackCommand,
+        value: Option<FallbackCommand>,
+    ) -> Result<()> {
+        let Some(value) = value else {
+            return Ok(());
+        };
+        if !value.rates_dps.iter().all(|r| r.is_finite()) || !value.climb_mps.is_finite() {
+            bail!(
+                "profile {} sets {field} to a non-finite command ({value:?})",
+                self.profile
+            )
+        }
+        let relaxes = value
+            .rates_dps
+            .iter()
+            .zip(base.rates_dps)
+            .any(|(rate, base)| rate.abs() > base.abs())
+            || value.climb_mps.abs() > base.climb_mps.abs();
+        self.apply(field, base, value, relaxes)
+    }
+
+    // Marks the `class_a_relaxation` entries covering `field` as used and
+    // reports whether there was one.
+    fn relaxed(&mut self, field: &str) -> bool {
+        let mut relaxed = false;
+        for (entry, used) in self.relax.iter().zip(&mut self.used) {
+            let covers = field
+                .strip_prefix(entry.as_str())
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with(['.', '[']));
+            if covers {
+                *used = true;
+                relaxed = true;
+            }
+        }
+        relaxed
+    }
+
+    fn apply<T: Debug>(
+        &mut self,
+        field: &str,
+        base: &mut T,
+        value: T,
+        relaxes: bool,
+    ) -> Result<()> {
+        if !self.relaxed(field) && relaxes {
+            bail!(
+                "profile {} relaxes Class A limit {field} ({:?} -> {:?}) without listing it under class_a_relaxation",
+                self.profile,
+                base,
+                value
+            )
+        }
+        *base = value;
+        Ok(())
+    }
+
+    fn verdict(&mut self, field: &str, base: &mut Verdict, value: Option<Verdict>) -> Result<()> {
+        let Some(value) = value else {
+            return Ok(());
+        };
+        if !self.relaxed(field) && value.precedence() < base.precedence() {
+            bail!(
+                "profile {} relaxes Class A verdict {field} ({:?} -> {:?}) without listing it under class_a_relaxation",
+                self.profile,
+                base,
+                value
+            )
+        }
+        *base = value;
+        Ok(())
+    }
+}
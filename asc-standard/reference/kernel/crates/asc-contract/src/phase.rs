@@ -0,0 +1,65 @@
+use anyhow::{bail, Result};
+use asc_types::model::FlightPhase;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Phases {
+    pub transitions: BTreeMap<FlightPhase, Vec<FlightPhase>>,
+    #[serde(default)]
+    pub overrides: BTreeMap<FlightPhase, PhaseOverride>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PhaseOverride {
+    pub invariants: Option<PhaseInvariantsOverride>,
+    pub flow: Option<PhaseFlowOverride>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PhaseInvariantsOverride {
+    pub min_altitude_m: Option<f64>,
+    pub max_bank_deg: Option<f64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PhaseFlowOverride {
+    pub max_roll_rate_dps: Option<f64>,
+    pub max_pitch_rate_dps: Option<f64>,
+    pub max_yaw_rate_dps: Option<f64>,
+    pub max_climb_rate_mps: Option<f64>,
+}
+
+pub fn validate_phases(phases: &Phases) -> Result<()> {
+    for (from, targets) in &phases.transitions {
+        if targets.contains(from) {
+            bail!("phase transition {from:?} -> {from:?} is a self-transition")
+        }
+    }
+    for (phase, o) in &phases.overrides {
+        if let Some(inv) = &o.invariants {
+            if inv.min_altitude_m.is_some_and(|v| v < 0.0) {
+                bail!("phase {phase:?} invariants.min_altitude_m must be >= 0")
+            }
+            if inv.max_bank_deg.is_some_and(|v| v <= 0.0) {
+                bail!("phase {phase:?} invariants.max_bank_deg must be > 0")
+            }
+        }
+        if let Some(flow) = &o.flow {
+            let limits = [
+                flow.max_roll_rate_dps,
+                flow.max_pitch_rate_dps,
+                flow.max_yaw_rate_dps,
+                flow.max_climb_rate_mps,
+            ];
+            if limits.iter().flatten().any(|v| *v <= 0.0) {
+                bail!("phase {phase:?} flow limits must be > 0")
+            }
+        }
+    }
+    Ok(())
+}
@@ -1,76 +1,608 @@
 use asc_types::{
-    model::{CheckOutcome, KernelInput},
+    model::{CheckId, CheckOutcome, Diagnostics, FlightPhase, KernelInput, TickAnomaly},
     ReasonCode, Severity, Verdict,
 };
 
-use crate::generated_thresholds as t;
-
-pub fn evaluate_checks(input: &KernelInput, inter_tick_ms: Option<u64>) -> Vec<CheckOutcome> {
-    let mut outcomes = Vec::new();
-
-    if input.state.frame != t::FRAME {
-        outcomes.push(CheckOutcome {
-            verdict: Verdict::Shutdown,
-            reason: ReasonCode::StateInvalidFrame,
-            severity: Severity::Critical,
-        });
-    }
-    if input.state.velocity_mps > t::MAX_SPEED_MPS {
-        outcomes.push(CheckOutcome {
-            verdict: Verdict::Clamp,
-            reason: ReasonCode::StateOutOfBounds,
-            severity: Severity::Warning,
-        });
-    }
-    if input.intent.desired_rates_dps[0].abs() > t::MAX_ROLL_RATE_DPS
-        || input.intent.desired_rates_dps[1].abs() > t::MAX_PITCH_RATE_DPS
-        || input.intent.desired_rates_dps[2].abs() > t::MAX_YAW_RATE_DPS
-        || input.intent.desired_climb_mps.abs() > t::MAX_CLIMB_RATE_MPS
-    {
-        outcomes.push(CheckOutcome {
-            verdict: Verdict::Clamp,
-            reason: ReasonCode::FlowConstraintViolation,
-            severity: Severity::Warning,
-        });
-    }
-    if input.state.soc_percent < t::MIN_SOC_PERCENT {
-        outcomes.push(CheckOutcome {
-            verdict: Verdict::Hold,
-            reason: ReasonCode::EnergyBudgetExceeded,
-            severity: Severity::Critical,
-        });
-    }
-    if input.state.input_age_ms > t::MAX_INPUT_AGE_MS {
-        outcomes.push(CheckOutcome {
-            verdict: Verdict::Hold,
-            reason: ReasonCode::InputStale,
-            severity: Severity::Critical,
-        });
-    }
-    if let Some(delta_ms) = inter_tick_ms {
-        if delta_ms > t::MAX_TICK_INTERVAL_MS {
-            outcomes.push(CheckOutcome {
-                verdict: Verdict::Override,
-                reason: ReasonCode::TemporalGuaranteeViolation,
-                severity: Severity::Critical,
-            });
+use crate::{
+    context::TickContext,
+    limits::{Envelope, Limits},
+    rcbf::Admissible,
+    sanitize::input_is_finite,
+    state::KernelState,
+};
+
+pub fn evaluate_checks(
+    input: &KernelInput,
+    limits: &Limits,
+    envelope: &Envelope,
+    registry: &CheckRegistry,
+    ctx: &TickContext,
+    state: &mut KernelState,
+) -> Diagnostics {
+    let mut outcomes = Diagnostics::new();
+    for outcome in [
+        Rule::new(
+            CheckId::InputFinite,
+            Verdict::Hold,
+            ReasonCode::NonFiniteInput,
+            Severity::Critical,
+        )
+        .flag(!input_is_finite(input)),
+        Rule::new(
+            CheckId::ShutdownLatch,
+            Verdict::Shutdown,
+            ReasonCode::ShutdownLatched,
+            Severity::Critical,
+        )
+        .flag(ctx.shutdown_latched),
+        Rule::new(
+            CheckId::Interlock,
+            Verdict::Hold,
+            ReasonCode::InterlockDisarmed,
+            severity_for(Verdict::Hold),
+        )
+        .flag(limits.interlock.armed_required && !input.state.armed),
+        Rule::new(
+            CheckId::Frame,
+            Verdict::Shutdown,
+            ReasonCode::StateInvalidFrame,
+            Severity::Critical,
+        )
+        .flag(input.state.frame != limits.state.frame),
+    ] {
+        record(&mut outcomes, outcome);
+    }
+    let max_speed_mps = if state.overspeed_engaged {
+        limits.state.max_speed_mps - limits.deescalation.speed_exit_band_mps
+    } else {
+        limits.state.max_speed_mps
+    };
+    let speed = Rule::new(
+        CheckId::Speed,
+        Verdict::Clamp,
+        ReasonCode::StateOutOfBounds,
+        Severity::Warning,
+    )
+    .upper(input.state.velocity_mps, max_speed_mps);
+    state.overspeed_engaged = speed.tripped;
+    record(&mut outcomes, speed);
+    let verdict = limits.state.position_bounds_verdict;
+    record(
+        &mut outcomes,
+        Rule::new(
+            CheckId::PositionBounds,
+            verdict,
+            ReasonCode::StateOutOfBounds,
+            severity_for(verdict),
+        )
+        .worst((0..3).flat_map(|axis| {
+            let position_m = input.state.position_m[axis];
+            let (min_m, max_m) = (
+                limits.state.position_min_m[axis],
+                limits.state.position_max_m[axis],
+            );
+            [
+                (position_m, min_m, position_m - min_m),
+                (position_m, max_m, max_m - position_m),
+            ]
+        })),
+    );
+    record(
+        &mut outcomes,
+        Rule::new(
+            CheckId::Attitude,
+            Verdict::Hold,
+            ReasonCode::StateOutOfBounds,
+            Severity::Critical,
+        )
+        .upper(
+            tilt_deg(input.state.bank_deg, input.state.pitch_deg),
+            limits.state.attitude_limit_deg,
+        ),
+    );
+    let rates = input.intent.desired_rates_dps;
+    let flow = &envelope.flow;
+    record(
+        &mut outcomes,
+        Rule::new(
+            CheckId::FlowRates,
+            Verdict::Clamp,
+            ReasonCode::FlowConstraintViolation,
+            Severity::Warning,
+        )
+        .worst(
+            [
+                (rates[0].abs(), flow.max_roll_rate_dps),
+                (rates[1].abs(), flow.max_pitch_rate_dps),
+                (rates[2].abs(), flow.max_yaw_rate_dps),
+                (
+                    input.intent.desired_climb_mps.abs(),
+                    flow.max_climb_rate_mps,
+                ),
+            ]
+            .map(|(measured, limit)| (measured, limit, limit - measured)),
+        ),
+    );
+    record(
+        &mut outcomes,
+        Rule::new(
+            CheckId::Barrier,
+            Verdict::Clamp,
+            ReasonCode::BarrierProjection,
+            Severity::Warning,
+        )
+        .worst([Admissible::from_state(&input.state, &envelope.invariants)
+            .binding(rates, input.intent.desired_climb_mps)]),
+    );
+    let min_soc_percent = if state.soc_floor_engaged {
+        limits.energy.min_soc_percent + limits.deescalation.soc_exit_band_percent
+    } else {
+        limits.energy.min_soc_percent
+    };
+    let soc = Rule::new(
+        CheckId::SocFloor,
+        Verdict::Hold,
+        ReasonCode::EnergyBudgetExceeded,
+        Severity::Critical,
+    )
+    .lower(input.state.soc_percent, min_soc_percent);
+    state.soc_floor_engaged = soc.tripped;
+    record(&mut outcomes, soc);
+    let endurance = Rule::new(
+        CheckId::Endurance,
+        Verdict::Hold,
+        ReasonCode::EnergyBudgetExceeded,
+        Severity::Critical,
+    );
+    record(
+        &mut outcomes,
+        match endurance_s(
+            input.state.soc_percent,
+            input.state.power_w,
+            input.state.battery_capacity_wh,
+        ) {
+            Some(endurance_s) => endurance.lower(endurance_s, limits.energy.reserve_endurance_s),
+            None => endurance.flag(false),
+        },
+    );
+    record(
+        &mut outcomes,
+        Rule::new(
+            CheckId::Power,
+            Verdict::Clamp,
+            ReasonCode::PowerLimitExceeded,
+            Severity::Warning,
+        )
+        .upper(input.state.power_w, limits.energy.max_power_w),
+    );
+    record(
+        &mut outcomes,
+        Rule::new(
+            CheckId::InputAge,
+            Verdict::Hold,
+            ReasonCode::InputStale,
+            Severity::Critical,
+        )
+        .upper(
+            input.state.input_age_ms as f64,
+            limits.guarantees.max_input_age_ms as f64,
+        ),
+    );
+    let sequence = Rule::new(
+        CheckId::TickSequence,
+        Verdict::Hold,
+        ReasonCode::TickSequenceViolation,
+        Severity::Critical,
+    );
+    let clock = Rule::new(
+        CheckId::Clock,
+        Verdict::Override,
+        ReasonCode::ClockRegression,
+        Severity::Critical,
+    );
+    let (mut sequence_seen, mut clock_seen) = (false, false);
+    for anomaly in &ctx.anomalies {
+        record(
+            &mut outcomes,
+            match anomaly {
+                TickAnomaly::DuplicateSeq { .. } | TickAnomaly::OutOfOrderSeq { .. } => {
+                    sequence_seen = true;
+                    sequence.flag(true)
+                }
+                TickAnomaly::SkippedSeq { expected_seq, seq } => {
+                    sequence_seen = true;
+                    Rule {
+                        reason: ReasonCode::InputStale,
+                        ..sequence
+                    }
+                    .upper(*seq as f64, *expected_seq as f64)
+                }
+                TickAnomaly::ClockRegression { ts_ms, last_ts_ms } => {
+                    clock_seen = true;
+                    clock.lower(*ts_ms as f64, *last_ts_ms as f64)
+                }
+            },
+        );
+    }
+    if !sequence_seen {
+        record(&mut outcomes, sequence.flag(false));
+    }
+    if !clock_seen {
+        record(&mut outcomes, clock.flag(false));
+    }
+    let temporal = Rule::new(
+        CheckId::TemporalFormula,
+        Verdict::Override,
+        ReasonCode::TemporalGuaranteeViolation,
+        Severity::Critical,
+    );
+    record(
+        &mut outcomes,
+        temporal.upper(ctx.temporal_violations as f64, 0.0),
+    );
+    let interval = Rule::new(
+        CheckId::TickInterval,
+        Verdict::Override,
+        ReasonCode::TemporalGuaranteeViolation,
+        Severity::Critical,
+    );
+    let deadline = Rule::new(
+        CheckId::Deadline,
+        Verdict::Override,
+        ReasonCode::DeadlineMiss,
+        Severity::Critical,
+    );
+    match ctx.inter_tick_ms {
+        Some(delta_ms) => {
+            record(
+                &mut outcomes,
+                interval.upper(
+                    delta_ms as f64,
+                    limits.guarantees.max_tick_interval_ms as f64,
+                ),
+            );
+            record(
+                &mut outcomes,
+                deadline.upper(delta_ms as f64, limits.guarantees.deadline_ms as f64),
+            );
         }
-        if delta_ms > t::DEADLINE_MS {
-            outcomes.push(CheckOutcome {
-                verdict: Verdict::Override,
-                reason: ReasonCode::DeadlineMiss,
-                severity: Severity::Critical,
-            });
+        None => {
+            record(&mut outcomes, interval.flag(false));
+            record(&mut outcomes, deadline.flag(false));
         }
     }
-    if input.state.position_m[2] < t::MIN_ALTITUDE_M || input.state.bank_deg.abs() > t::MAX_BANK_DEG
-    {
-        outcomes.push(CheckOutcome {
-            verdict: Verdict::Shutdown,
-            reason: ReasonCode::InvariantViolation,
-            severity: Severity::Critical,
-        });
+    record(
+        &mut outcomes,
+        Rule::new(
+            CheckId::Geofence,
+            Verdict::Override,
+            ReasonCode::GeofenceViolation,
+            Severity::Critical,
+        )
+        .flag(
+            limits
+                .geofence
+                .as_ref()
+                .is_some_and(|fence| fence.breached_zone(input.state.position_m).is_some()),
+        ),
+    );
+    record(
+        &mut outcomes,
+        Rule::new(
+            CheckId::MinAltitude,
+            Verdict::Shutdown,
+            ReasonCode::InvariantViolation,
+            Severity::Critical,
+        )
+        .lower(input.state.altitude_m(), envelope.invariants.min_altitude_m),
+    );
+    record(
+        &mut outcomes,
+        Rule::new(
+            CheckId::MaxBank,
+            Verdict::Shutdown,
+            ReasonCode::InvariantViolation,
+            Severity::Critical,
+        )
+        .upper(input.state.bank_deg.abs(), envelope.invariants.max_bank_deg),
+    );
+    let caps = &limits.capabilities;
+    let airspeed = Rule::new(
+        CheckId::Airspeed,
+        Verdict::Override,
+        ReasonCode::AirspeedBelowStall,
+        Severity::Critical,
+    );
+    // The ground roll, rotation and flare run below stall speed, so the check
+    // does not apply in a validated Ground, Takeoff or Landing phase. It
+    // compares the reported airspeed, never ground speed; a missing reading
+    // cannot clear it.
+    let can_stall = caps.fixed_wing
+        && !caps.vtol
+        && !(ctx.phase_validated
+            && matches!(
+                ctx.phase,
+                Some(FlightPhase::Ground | FlightPhase::Takeoff | FlightPhase::Landing)
+            ));
+    record(
+        &mut outcomes,
+        match (caps.min_airspeed_mps, input.state.airspeed_mps) {
+            (Some(min_airspeed_mps), Some(airspeed_mps)) if can_stall => {
+                airspeed.lower(airspeed_mps, min_airspeed_mps)
+            }
+            (Some(_), None) if can_stall => airspeed.flag(true),
+            _ => airspeed.flag(false),
+        },
+    );
+    let hover_descent = Rule::new(
+        CheckId::HoverDescent,
+        Verdict::Clamp,
+        ReasonCode::FlowConstraintViolation,
+        Severity::Warning,
+    );
+    record(
+        &mut outcomes,
+        match caps.hover_descent_limit_mps(input.state.airspeed_mps) {
+            Some(max_descent_mps) => {
+                hover_descent.upper(-input.intent.desired_climb_mps, max_descent_mps)
+            }
+            None => hover_descent.flag(false),
+        },
+    );
+    record(
+        &mut outcomes,
+        Rule::new(
+            CheckId::Payload,
+            Verdict::Hold,
+            ReasonCode::PayloadExceeded,
+            Severity::Critical,
+        )
+        .upper(input.state.payload_kg, caps.max_payload_kg),
+    );
+    record(
+        &mut outcomes,
+        Rule::new(
+            CheckId::Phase,
+            Verdict::Hold,
+            ReasonCode::PhaseTransitionInvalid,
+            Severity::Critical,
+        )
+        .flag(ctx.phase_rejected),
+    );
+    record(
+        &mut outcomes,
+        Rule::new(
+            CheckId::EventLog,
+            Verdict::Hold,
+            ReasonCode::EventLogFault,
+            Severity::Critical,
+        )
+        .flag(ctx.log_fault),
+    );
+    for (index, (check, params)) in registry.iter().enumerate() {
+        let rule = Rule::new(
+            CheckId::Program(index as u16),
+            check.verdict(),
+            check.reason(),
+            check.severity(),
+        );
+        record(
+            &mut outcomes,
+            rule.read(check.evaluate(input, limits, params)),
+        );
     }
 
     outcomes
 }
+
+// Upper bound on program checks; together with the built-in checks it stays
+// within MAX_DIAGNOSTICS.
+pub const MAX_PROGRAM_CHECKS: usize = 16;
+pub const MAX_CHECK_PARAMS: usize = 8;
+pub const CHECK_PARAM_NAME_CAPACITY: usize = 32;
+
+// A program-specific check evaluated after the built-in checks. The kernel
+// builds its outcome from the declared verdict, reason and severity, so a check
+// only reports what it measured. It reads the parameters declared with it and
+// any signals in `KernelInput::extension`.
+pub trait Check: Send + Sync {
+    // Stable identity, matched against the id declared in spec/asc/checks.yaml.
+    fn name(&self) -> &str;
+    // Implementation version, covered by the runtime's contract fingerprint.
+    fn version(&self) -> &str;
+    fn verdict(&self) -> Verdict;
+    fn reason(&self) -> ReasonCode;
+    fn severity(&self) -> Severity;
+    fn evaluate(&self, input: &KernelInput, limits: &Limits, params: &CheckParams) -> Reading;
+}
+
+// Named parameters declared with a program check in spec/asc/checks.yaml.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CheckParams {
+    entries: heapless::Vec<(heapless::String<CHECK_PARAM_NAME_CAPACITY>, f64), MAX_CHECK_PARAMS>,
+}
+
+impl CheckParams {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Hands the value back once the name or the parameter does not fit.
+    pub fn insert(&mut self, name: &str, value: f64) -> Result<(), f64> {
+        let name = heapless::String::try_from(name).map_err(|()| value)?;
+        self.entries.push((name, value)).map_err(|(_, value)| value)
+    }
+
+    pub fn get(&self, name: &str) -> Option<f64> {
+        self.entries
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| *value)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Reading {
+    Flag(bool),
+    // Trips once `measured` exceeds `limit`.
+    Upper { measured: f64, limit: f64 },
+    // Trips once `measured` falls below `limit`.
+    Lower { measured: f64, limit: f64 },
+}
+
+// Program checks in evaluation order. A check's position is its
+// `CheckId::Program` index in the diagnostics.
+#[derive(Default)]
+pub struct CheckRegistry<'a> {
+    checks: heapless::Vec<(&'a dyn Check, &'a CheckParams), MAX_PROGRAM_CHECKS>,
+}
+
+impl<'a> CheckRegistry<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Hands the check back once MAX_PROGRAM_CHECKS are registered.
+    pub fn register(
+        &mut self,
+        check: &'a dyn Check,
+        params: &'a CheckParams,
+    ) -> Result<(), &'a dyn Check> {
+        self.checks
+            .push((check, params))
+            .map_err(|(check, _)| check)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&'a dyn Check, &'a CheckParams)> + '_ {
+        self.checks.iter().copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.checks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.checks.is_empty()
+    }
+}
+
+// Every check reports exactly one outcome per tick, which MAX_DIAGNOSTICS
+// covers, so running out of capacity is a kernel defect.
+pub(crate) fn record(outcomes: &mut Diagnostics, outcome: CheckOutcome) {
+    outcomes
+        .push(outcome)
+        .expect("MAX_DIAGNOSTICS covers every check");
+}
+
+#[derive(Clone, Copy)]
+pub(crate) struct Rule {
+    check: CheckId,
+    verdict: Verdict,
+    reason: ReasonCode,
+    severity: Severity,
+}
+
+impl Rule {
+    pub(crate) fn new(
+        check: CheckId,
+        verdict: Verdict,
+        reason: ReasonCode,
+        severity: Severity,
+    ) -> Self {
+        Self {
+            check,
+            verdict,
+            reason,
+            severity,
+        }
+    }
+
+    fn read(self, reading: Reading) -> CheckOutcome {
+        match reading {
+            Reading::Flag(tripped) => self.flag(tripped),
+            Reading::Upper { measured, limit } => self.upper(measured, limit),
+            Reading::Lower { measured, limit } => self.lower(measured, limit),
+        }
+    }
+
+    pub(crate) fn flag(self, tripped: bool) -> CheckOutcome {
+        self.outcome(tripped, None, None, None)
+    }
+
+    fn upper(self, measured: f64, limit: f64) -> CheckOutcome {
+        self.outcome(
+            measured > limit,
+            Some(measured),
+            Some(limit),
+            Some(limit - measured),
+        )
+    }
+
+    fn lower(self, measured: f64, limit: f64) -> CheckOutcome {
+        self.outcome(
+            measured < limit,
+            Some(measured),
+            Some(limit),
+            Some(measured - limit),
+        )
+    }
+
+    // Reports the (measured, limit, margin) bound with the smallest margin.
+    pub(crate) fn worst(self, bounds: impl IntoIterator<Item = (f64, f64, f64)>) -> CheckOutcome {
+        match bounds.into_iter().min_by(|a, b| a.2.total_cmp(&b.2)) {
+            Some((measured, limit, margin)) => {
+                self.outcome(margin < 0.0, Some(measured), Some(limit), Some(margin))
+            }
+            None => self.flag(false),
+        }
+    }
+
+    fn outcome(
+        self,
+        tripped: bool,
+        measured: Option<f64>,
+        limit: Option<f64>,
+        margin: Option<f64>,
+    ) -> CheckOutcome {
+        let finite = |v: Option<f64>| v.filter(|v| v.is_finite());
+        CheckOutcome {
+            check: self.check,
+            verdict: if tripped {
+                self.verdict
+            } else {
+                Verdict::Allow
+            },
+            reason: self.reason,
+            severity: if tripped {
+                self.severity
+            } else {
+                Severity::Info
+            },
+            tripped,
+            measured: finite(measured),
+            limit: finite(limit),
+            margin: finite(margin),
+        }
+    }
+}
+
+fn endurance_s(soc_percent: f64, power_w: f64, battery_capacity_wh: f64) -> Option<f64> {
+    if power_w <= 0.0 {
+        return None;
+    }
+    let remaining_wh = battery_capacity_wh * soc_percent.max(0.0) / 100.0;
+    Some(remaining_wh * 3600.0 / power_w)
+}
+
+fn tilt_deg(bank_deg: f64, pitch_deg: f64) -> f64 {
+    // libm keeps the result bit-identical across targets, with or without std.
+    let cos_tilt = libm::cos(bank_deg.to_radians()) * libm::cos(pitch_deg.to_radians());
+    libm::acos(cos_tilt.clamp(-1.0, 1.0)).to_degrees()
+}
+
+fn severity_for(verdict: Verdict) -> Severity {
+    match verdict {
+        Verdict::Allow | Verdict::Clamp => Severity::Warning,
+        Verdict::Hold | Verdict::Override | Verdict::Shutdown => Severity::Critical,
+    }
+}
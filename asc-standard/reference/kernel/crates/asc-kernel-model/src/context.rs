@@ -0,0 +1,18 @@
+use asc_types::model::{FlightPhase, TickAnomaly};
+use serde::{Deserialize, Serialize};
+
+// At most one sequence anomaly and one clock anomaly per tick.
+pub const MAX_TICK_ANOMALIES: usize = 2;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TickContext {
+    pub inter_tick_ms: Option<u64>,
+    pub shutdown_latched: bool,
+    pub anomalies: heapless::Vec<TickAnomaly, MAX_TICK_ANOMALIES>,
+    pub temporal_violations: usize,
+    pub phase: Option<FlightPhase>,
+    pub phase_validated: bool,
+    pub phase_rejected: bool,
+    // The event log is failing to store or has dropped records.
+    pub log_fault: bool,
+}
@@ -1,20 +1,81 @@
-use asc_types::model::{ConstrainedCommand, KernelInput, KernelOutput};
-use asc_types::Verdict;
+use asc_types::model::{
+    CheckId, CheckOutcome, ConstrainedCommand, Fingerprint, KernelInput, KernelOutput,
+};
+use asc_types::{ReasonCode, Severity, Verdict};
 
-use crate::{arbitration::decide, checks::evaluate_checks, generated_thresholds as t};
+use crate::{
+    arbitration::decide,
+    checks::{evaluate_checks, record, CheckRegistry, Rule},
+    context::TickContext,
+    hysteresis::sustain,
+    limits::{FallbackCommand, Limits},
+    rcbf::Admissible,
+    sanitize::finite_command,
+    state::KernelState,
+};
 
-pub fn constrain(input: &KernelInput, inter_tick_ms: Option<u64>) -> KernelOutput {
-    let outcomes = evaluate_checks(input, inter_tick_ms);
-    let verdict = decide(&outcomes);
+pub fn constrain(
+    input: &KernelInput,
+    limits: &Limits,
+    registry: &CheckRegistry,
+    ctx: &TickContext,
+    state: &mut KernelState,
+) -> KernelOutput {
+    let envelope = limits.envelope(ctx.phase, ctx.phase_validated, input.state.airspeed_mps);
+    let mut outcomes = evaluate_checks(input, limits, &envelope, registry, ctx, state);
+    let raw_verdict = decide(&outcomes);
+    let held = sustain(state, raw_verdict, input.tick.ts_ms, &limits.deescalation);
+    record(
+        &mut outcomes,
+        Rule::new(
+            CheckId::Deescalation,
+            held.unwrap_or(Verdict::Allow),
+            ReasonCode::DeescalationPending,
+            Severity::Info,
+        )
+        .flag(held.is_some()),
+    );
+    let mut verdict = decide(&outcomes);
 
+    // The pass-through intent is slewed from the previous tick's applied
+    // command first, so that the barrier, flow and hover-descent limits below
+    // always have the last word. Fallback commands apply immediately but are
+    // still projected through the barrier.
     let mut rates = input.intent.desired_rates_dps;
-    rates[0] = rates[0].clamp(-t::MAX_ROLL_RATE_DPS, t::MAX_ROLL_RATE_DPS);
-    rates[1] = rates[1].clamp(-t::MAX_PITCH_RATE_DPS, t::MAX_PITCH_RATE_DPS);
-    rates[2] = rates[2].clamp(-t::MAX_YAW_RATE_DPS, t::MAX_YAW_RATE_DPS);
-    let climb = input
-        .intent
-        .desired_climb_mps
-        .clamp(-t::MAX_CLIMB_RATE_MPS, t::MAX_CLIMB_RATE_MPS);
+    let mut climb = input.intent.desired_climb_mps;
+    let flow = &envelope.flow;
+    let slew = Rule::new(
+        CheckId::Slew,
+        Verdict::Clamp,
+        ReasonCode::SlewLimited,
+        Severity::Warning,
+    );
+    match (&state.last_command, verdict) {
+        (Some(last), Verdict::Allow | Verdict::Clamp) => {
+            let last_rates = last.applied_rates_dps;
+            let max_step = flow.max_rate_step_dps;
+            let bounds = [
+                slew_toward(&mut rates[0], last_rates[0], max_step),
+                slew_toward(&mut rates[1], last_rates[1], max_step),
+                slew_toward(&mut rates[2], last_rates[2], max_step),
+                slew_toward(&mut climb, last.applied_climb_mps, flow.max_climb_step_mps),
+            ];
+            record(&mut outcomes, slew.worst(bounds));
+            verdict = decide(&outcomes);
+        }
+        _ => record(&mut outcomes, slew.flag(false)),
+    }
+
+    let barrier = Admissible::from_state(&input.state, &envelope.invariants);
+    let (mut rates, climb) = barrier.project(rates, climb);
+    rates[0] = rates[0].clamp(-flow.max_roll_rate_dps, flow.max_roll_rate_dps);
+    rates[1] = rates[1].clamp(-flow.max_pitch_rate_dps, flow.max_pitch_rate_dps);
+    rates[2] = rates[2].clamp(-flow.max_yaw_rate_dps, flow.max_yaw_rate_dps);
+    let mut climb = climb.clamp(-flow.max_climb_rate_mps, flow.max_climb_rate_mps);
+    let airspeed_mps = input.state.airspeed_mps;
+    if let Some(max_descent_mps) = limits.capabilities.hover_descent_limit_mps(airspeed_mps) {
+        climb = climb.max(-max_descent_mps);
+    }
 
     let command = match verdict {
         Verdict::Allow | Verdict::Clamp => ConstrainedCommand {
@@ -22,27 +83,56 @@ pub fn constrain(input: &KernelInput, inter_tick_ms: Option<u64>) -> KernelOutpu
             applied_climb_mps: climb,
             shutdown: false,
         },
-        Verdict::Hold => ConstrainedCommand {
-            applied_rates_dps: [0.0, 0.0, 0.0],
-            applied_climb_mps: 0.0,
-            shutdown: false,
-        },
-        Verdict::Override => ConstrainedCommand {
-            applied_rates_dps: [0.0, 0.0, 0.0],
-            applied_climb_mps: -1.0,
-            shutdown: false,
-        },
+        Verdict::Hold if limits.capabilities.wingborne(airspeed_mps) => {
+            fallback(&limits.fallbacks.loiter, &barrier)
+        }
+        Verdict::Hold => fallback(&limits.fallbacks.hold, &barrier),
+        Verdict::Override if stalled(&outcomes) => fallback(&limits.fallbacks.stall, &barrier),
+        Verdict::Override => fallback(&limits.fallbacks.r#override, &barrier),
         Verdict::Shutdown => ConstrainedCommand {
-            applied_rates_dps: [0.0, 0.0, 0.0],
-            applied_climb_mps: 0.0,
+            applied_rates_dps: limits.fallbacks.shutdown.rates_dps,
+            applied_climb_mps: limits.fallbacks.shutdown.climb_mps,
             shutdown: true,
         },
     };
 
+    let command = finite_command(command);
+    state.last_command = Some(command.clone());
+
     KernelOutput {
         verdict,
-        reasons: outcomes.iter().map(|o| o.reason).collect(),
+        reasons: outcomes
+            .iter()
+            .filter(|o| o.tripped)
+            .map(|o| o.reason)
+            .collect(),
+        diagnostics: outcomes,
         command,
-        contract_fingerprint: String::new(),
+        contract_fingerprint: Fingerprint::new(),
     }
 }
+
+fn fallback(command: &FallbackCommand, barrier: &Admissible) -> ConstrainedCommand {
+    let (rates, climb) = barrier.project(command.rates_dps, command.climb_mps);
+    ConstrainedCommand {
+        applied_rates_dps: rates,
+        applied_climb_mps: climb,
+        shutdown: false,
+    }
+}
+
+// A stalling aircraft must lower its nose and descend to regain airspeed, so
+// the stall check selects its own Override command.
+fn stalled(outcomes: &[CheckOutcome]) -> bool {
+    outcomes
+        .iter()
+        .any(|o| o.check == CheckId::Airspeed && o.tripped)
+}
+
+// Limits `value` to within `max_step` of `last` and returns the
+// (step, max_step, margin) bound for the slew diagnostic.
+fn slew_toward(value: &mut f64, last: f64, max_step: f64) -> (f64, f64, f64) {
+    let step = (*value - last).abs();
+    *value = value.max(last - max_step).min(last + max_step);
+    (step, max_step, max_step - step)
+}
@@ -1,7 +1,36 @@
 // @generated by tools/specgen. DO NOT EDIT.
 
+use crate::geofence::{BakedGeofence, BakedShape, BakedZone};
+use crate::transition::TransitionLimits;
+
 pub const PROFILE_NAME: &str = "uas-small";
 pub const CONTROL_HZ: u64 = 50;
 pub const PROFILE_DEADLINE_MS: u64 = 10;
 pub const CAP_VTOL: bool = false;
 pub const CAP_FIXED_WING: bool = true;
+pub const CAP_MAX_PAYLOAD_KG: f64 = 2.5;
+pub const CAP_MIN_AIRSPEED_MPS: Option<f64> = Some(12.0);
+pub const CAP_MAX_HOVER_DESCENT_MPS: Option<f64> = None;
+pub const TRANSITION: Option<TransitionLimits> = None;
+pub const GEOFENCE: Option<BakedGeofence> = Some(BakedGeofence {
+    inclusion: BakedZone {
+        id: "reference-range",
+        shape: BakedShape::Polygon(&[
+            [-4000.0, -4000.0],
+            [4000.0, -4000.0],
+            [4000.0, 4000.0],
+            [-4000.0, 4000.0],
+        ]),
+        floor_m: 0.0,
+        ceiling_m: 100.0,
+    },
+    exclusions: &[BakedZone {
+        id: "mast-north",
+        shape: BakedShape::Cylinder {
+            center_m: [1500.0, 1500.0],
+            radius_m: 200.0,
+        },
+        floor_m: 0.0,
+        ceiling_m: 60.0,
+    }],
+});
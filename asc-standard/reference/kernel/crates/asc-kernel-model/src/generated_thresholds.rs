@@ -1,10 +1,21 @@
 // @generated by tools/specgen. DO NOT EDIT.
 
+use asc_types::{model::FlightPhase, Verdict};
+
+use crate::limits::PhaseLimits;
+
 pub const FRAME: &str = "NED";
+pub const POSITION_MIN_M: [f64; 3] = [-5000.0, -5000.0, -1000.0];
+pub const POSITION_MAX_M: [f64; 3] = [5000.0, 5000.0, 100.0];
+pub const POSITION_BOUNDS_VERDICT: Verdict = Verdict::Hold;
+pub const ATTITUDE_LIMIT_DEG: f64 = 55.0;
 pub const MAX_SPEED_MPS: f64 = 120.0;
 pub const MAX_BANK_DEG: f64 = 60.0;
 pub const MIN_ALTITUDE_M: f64 = 5.0;
+pub const BARRIER_GAIN_PER_S: f64 = 1.0;
 pub const MIN_SOC_PERCENT: f64 = 20.0;
+pub const RESERVE_ENDURANCE_S: f64 = 180.0;
+pub const MAX_POWER_W: f64 = 2500.0;
 pub const MAX_INPUT_AGE_MS: u64 = 100;
 pub const MAX_TICK_INTERVAL_MS: u64 = 20;
 pub const DEADLINE_MS: u64 = 10;
@@ -12,3 +23,71 @@ pub const MAX_ROLL_RATE_DPS: f64 = 120.0;
 pub const MAX_PITCH_RATE_DPS: f64 = 100.0;
 pub const MAX_YAW_RATE_DPS: f64 = 80.0;
 pub const MAX_CLIMB_RATE_MPS: f64 = 8.0;
+pub const MAX_RATE_STEP_DPS: f64 = 30.0;
+pub const MAX_CLIMB_STEP_MPS: f64 = 1.0;
+pub const ARMED_REQUIRED: bool = true;
+pub const FAULT_LATCHED_SHUTDOWN: bool = true;
+pub const RESET_AUTHORITIES: &[&str] = &["ground-station"];
+pub const DEESCALATION_CLEAN_TICKS: u32 = 5;
+pub const DEESCALATION_MIN_DWELL_MS: u64 = 100;
+pub const SOC_EXIT_BAND_PERCENT: f64 = 2.0;
+pub const SPEED_EXIT_BAND_MPS: f64 = 2.0;
+pub const HOLD_RATES_DPS: [f64; 3] = [0.0, 0.0, 0.0];
+pub const HOLD_CLIMB_MPS: f64 = 0.0;
+pub const OVERRIDE_RATES_DPS: [f64; 3] = [0.0, 0.0, 0.0];
+pub const OVERRIDE_CLIMB_MPS: f64 = -1.0;
+pub const SHUTDOWN_RATES_DPS: [f64; 3] = [0.0, 0.0, 0.0];
+pub const SHUTDOWN_CLIMB_MPS: f64 = 0.0;
+pub const LOITER_RATES_DPS: [f64; 3] = [0.0, 0.0, 6.0];
+pub const LOITER_CLIMB_MPS: f64 = 0.0;
+pub const STALL_RATES_DPS: [f64; 3] = [0.0, 0.0, 0.0];
+pub const STALL_CLIMB_MPS: f64 = -2.0;
+pub const STL_FORMULAS: &[(&str, &str)] = &[];
+pub const PHASE_TRANSITIONS: &[(FlightPhase, FlightPhase)] = &[
+    (FlightPhase::Ground, FlightPhase::Takeoff),
+    (FlightPhase::Takeoff, FlightPhase::Cruise),
+    (FlightPhase::Takeoff, FlightPhase::Landing),
+    (FlightPhase::Cruise, FlightPhase::Approach),
+    (FlightPhase::Approach, FlightPhase::Cruise),
+    (FlightPhase::Approach, FlightPhase::Landing),
+    (FlightPhase::Landing, FlightPhase::Ground),
+    (FlightPhase::Landing, FlightPhase::Takeoff),
+];
+pub const PHASE_OVERRIDES: &[PhaseLimits] = &[
+    PhaseLimits {
+        phase: FlightPhase::Ground,
+        min_altitude_m: Some(0.0),
+        max_bank_deg: None,
+        max_roll_rate_dps: None,
+        max_pitch_rate_dps: None,
+        max_yaw_rate_dps: None,
+        max_climb_rate_mps: None,
+    },
+    PhaseLimits {
+        phase: FlightPhase::Takeoff,
+        min_altitude_m: Some(0.0),
+        max_bank_deg: Some(20.0),
+        max_roll_rate_dps: None,
+        max_pitch_rate_dps: None,
+        max_yaw_rate_dps: None,
+        max_climb_rate_mps: None,
+    },
+    PhaseLimits {
+        phase: FlightPhase::Approach,
+        min_altitude_m: None,
+        max_bank_deg: None,
+        max_roll_rate_dps: None,
+        max_pitch_rate_dps: None,
+        max_yaw_rate_dps: None,
+        max_climb_rate_mps: Some(5.0),
+    },
+    PhaseLimits {
+        phase: FlightPhase::Landing,
+        min_altitude_m: Some(0.0),
+        max_bank_deg: Some(20.0),
+        max_roll_rate_dps: None,
+        max_pitch_rate_dps: None,
+        max_yaw_rate_dps: None,
+        max_climb_rate_mps: Some(3.0),
+    },
+];
@@ -0,0 +1,136 @@
+use serde::{Deserialize, Serialize};
+
+pub const MAX_FENCE_VERTICES: usize = 32;
+pub const MAX_FENCE_EXCLUSIONS: usize = 8;
+pub const FENCE_ID_CAPACITY: usize = 32;
+
+// Polygons use fixed-capacity storage so the kernel needs no allocator; fences
+// are built once per contract, so the variant size difference is accepted.
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum FenceShape {
+    Polygon {
+        vertices_m: heapless::Vec<[f64; 2], MAX_FENCE_VERTICES>,
+    },
+    Cylinder {
+        center_m: [f64; 2],
+        radius_m: f64,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FenceZone {
+    pub id: heapless::String<FENCE_ID_CAPACITY>,
+    pub shape: FenceShape,
+    pub floor_m: f64,
+    pub ceiling_m: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Geofence {
+    pub inclusion: FenceZone,
+    pub exclusions: heapless::Vec<FenceZone, MAX_FENCE_EXCLUSIONS>,
+}
+
+// Borrowed zone data baked in by specgen, converted into a `FenceZone` when
+// the baked limits are built.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BakedShape {
+    Polygon(&'static [[f64; 2]]),
+    Cylinder { center_m: [f64; 2], radius_m: f64 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BakedZone {
+    pub id: &'static str,
+    pub shape: BakedShape,
+    pub floor_m: f64,
+    pub ceiling_m: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BakedGeofence {
+    pub inclusion: BakedZone,
+    pub exclusions: &'static [BakedZone],
+}
+
+impl FenceZone {
+    // `position_m` is NED, while the floor and ceiling are altitudes.
+    pub fn contains(&self, position_m: [f64; 3]) -> bool {
+        let altitude_m = -position_m[2];
+        if altitude_m < self.floor_m || altitude_m > self.ceiling_m {
+            return false;
+        }
+        let point = [position_m[0], position_m[1]];
+        match &self.shape {
+            FenceShape::Polygon { vertices_m } => polygon_contains(vertices_m, point),
+            FenceShape::Cylinder { center_m, radius_m } => {
+                let dn = point[0] - center_m[0];
+                let de = point[1] - center_m[1];
+                dn * dn + de * de <= radius_m * radius_m
+            }
+        }
+    }
+}
+
+impl BakedZone {
+    // None when the zone exceeds a kernel capacity; specgen rejects such zones.
+    pub fn to_zone(&self) -> Option<FenceZone> {
+        Some(FenceZone {
+            id: heapless::String::try_from(self.id).ok()?,
+            shape: match self.shape {
+                BakedShape::Polygon(vertices_m) => FenceShape::Polygon {
+                    vertices_m: heapless::Vec::from_slice(vertices_m).ok()?,
+                },
+                BakedShape::Cylinder { center_m, radius_m } => {
+                    FenceShape::Cylinder { center_m, radius_m }
+                }
+            },
+            floor_m: self.floor_m,
+            ceiling_m: self.ceiling_m,
+        })
+    }
+}
+
+impl BakedGeofence {
+    pub fn to_geofence(&self) -> Option<Geofence> {
+        let mut exclusions = heapless::Vec::new();
+        for zone in self.exclusions {
+            exclusions.push(zone.to_zone()?).ok()?;
+        }
+        Some(Geofence {
+            inclusion: self.inclusion.to_zone()?,
+            exclusions,
+        })
+    }
+}
+
+impl Geofence {
+    pub fn breached_zone(&self, position_m: [f64; 3]) -> Option<&FenceZone> {
+        if !self.inclusion.contains(position_m) {
+            return Some(&self.inclusion);
+        }
+        self.exclusions
+            .iter()
+            .find(|zone| zone.contains(position_m))
+    }
+}
+
+fn polygon_contains(vertices_m: &[[f64; 2]], point: [f64; 2]) -> bool {
+    let Some(&last) = vertices_m.last() else {
+        return false;
+    };
+    let mut inside = false;
+    let mut prev = last;
+    for &curr in vertices_m {
+        if (curr[1] > point[1]) != (prev[1] > point[1]) {
+            let cross_n =
+                (prev[0] - curr[0]) * (point[1] - curr[1]) / (prev[1] - curr[1]) + curr[0];
+            if point[0] < cross_n {
+                inside = !inside;
+            }
+        }
+        prev = curr;
+    }
+    inside
+}
@@ -0,0 +1,52 @@
+use asc_types::Verdict;
+
+use crate::{
+    limits::DeescalationLimits,
+    state::{HeldVerdict, KernelState},
+};
+
+pub fn sustain(
+    state: &mut KernelState,
+    raw: Verdict,
+    ts_ms: u64,
+    rule: &DeescalationLimits,
+) -> Option<Verdict> {
+    let hold_from = |verdict: Verdict| {
+        matches!(verdict, Verdict::Hold | Verdict::Override).then_some(HeldVerdict {
+            verdict,
+            since_ts_ms: ts_ms,
+            clean_ticks: 0,
+        })
+    };
+
+    let Some(held) = state.held else {
+        state.held = hold_from(raw);
+        return None;
+    };
+    if raw.precedence() >= held.verdict.precedence() {
+        state.held = hold_from(raw);
+        return None;
+    }
+
+    // A tick that trips any check, even one below the held verdict, is not
+    // clean and restarts both the count and the dwell.
+    if raw != Verdict::Allow {
+        state.held = Some(HeldVerdict {
+            since_ts_ms: ts_ms,
+            clean_ticks: 0,
+            ..held
+        });
+        return Some(held.verdict);
+    }
+    let clean_ticks = held.clean_ticks.saturating_add(1);
+    let dwell_ms = ts_ms.saturating_sub(held.since_ts_ms);
+    if clean_ticks >= rule.clean_ticks && dwell_ms >= rule.min_dwell_ms {
+        state.held = hold_from(raw);
+        return None;
+    }
+    state.held = Some(HeldVerdict {
+        clean_ticks,
+        ..held
+    });
+    Some(held.verdict)
+}
@@ -1,7 +1,26 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 pub mod arbitration;
 pub mod checks;
+pub mod context;
 pub mod engine;
 pub mod generated_profile;
 pub mod generated_thresholds;
+pub mod geofence;
+pub mod hysteresis;
+pub mod limits;
+pub mod rcbf;
+pub mod sanitize;
+pub mod state;
+#[cfg(feature = "alloc")]
+pub mod stl;
+pub mod transition;
 
+pub use checks::{Check, CheckParams, CheckRegistry, Reading};
+pub use context::TickContext;
 pub use engine::constrain;
+pub use limits::{Envelope, Limits};
+pub use state::KernelState;
@@ -0,0 +1,344 @@
+#[cfg(feature = "alloc")]
+use alloc::{string::ToString, vec::Vec};
+use asc_types::{
+    model::{FlightPhase, Frame},
+    Verdict,
+};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "alloc")]
+use crate::stl::{Formula, StlFormula};
+use crate::{
+    generated_profile as p, generated_thresholds as t, geofence::Geofence,
+    transition::TransitionLimits,
+};
+
+pub const MAX_PHASE_TRANSITIONS: usize = 25;
+pub const MAX_PHASE_OVERRIDES: usize = 5;
+pub const MAX_RESET_AUTHORITIES: usize = 4;
+pub const AUTHORITY_CAPACITY: usize = 32;
+
+pub type Authority = heapless::String<AUTHORITY_CAPACITY>;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StateLimits {
+    pub frame: Frame,
+    pub position_min_m: [f64; 3],
+    pub position_max_m: [f64; 3],
+    pub position_bounds_verdict: Verdict,
+    pub attitude_limit_deg: f64,
+    pub max_speed_mps: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FlowLimits {
+    pub max_roll_rate_dps: f64,
+    pub max_pitch_rate_dps: f64,
+    pub max_yaw_rate_dps: f64,
+    pub max_climb_rate_mps: f64,
+    pub max_rate_step_dps: f64,
+    pub max_climb_step_mps: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EnergyLimits {
+    pub min_soc_percent: f64,
+    pub reserve_endurance_s: f64,
+    pub max_power_w: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GuaranteeLimits {
+    pub max_input_age_ms: u64,
+    pub max_tick_interval_ms: u64,
+    pub deadline_ms: u64,
+    // STL formulas need an allocator; the monitor runs in the runtime and
+    // reports violations through TickContext.
+    #[cfg(feature = "alloc")]
+    pub formulas: Vec<StlFormula>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct InvariantLimits {
+    pub min_altitude_m: f64,
+    pub max_bank_deg: f64,
+    pub barrier_gain_per_s: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InterlockLimits {
+    pub armed_required: bool,
+    pub fault_latched_shutdown: bool,
+    pub reset_authorities: heapless::Vec<Authority, MAX_RESET_AUTHORITIES>,
+}
+
+impl InterlockLimits {
+    pub fn may_reset(&self, authority: &str) -> bool {
+        self.reset_authorities.iter().any(|a| a == authority)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeescalationLimits {
+    pub clean_ticks: u32,
+    pub min_dwell_ms: u64,
+    pub soc_exit_band_percent: f64,
+    pub speed_exit_band_mps: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CapabilityLimits {
+    pub vtol: bool,
+    pub fixed_wing: bool,
+    pub max_payload_kg: f64,
+    pub min_airspeed_mps: Option<f64>,
+    pub max_hover_descent_mps: Option<f64>,
+}
+
+impl CapabilityLimits {
+    // A fixed-wing airframe is wingborne unless it can hover and is below its
+    // stall speed; a wingborne vehicle cannot hold position with zero rates.
+    // Without an airspeed reading an airframe that can hover is assumed to.
+    pub fn wingborne(&self, airspeed_mps: Option<f64>) -> bool {
+        self.fixed_wing
+            && (!self.vtol
+                || self.min_airspeed_mps.is_some_and(|min_airspeed_mps| {
+                    airspeed_mps.is_some_and(|airspeed_mps| airspeed_mps >= min_airspeed_mps)
+                }))
+    }
+
+    pub fn hover_descent_limit_mps(&self, airspeed_mps: Option<f64>) -> Option<f64> {
+        if self.vtol && !self.wingborne(airspeed_mps) {
+            self.max_hover_descent_mps
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FallbackCommand {
+    pub rates_dps: [f64; 3],
+    pub climb_mps: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FallbackLimits {
+    pub hold: FallbackCommand,
+    pub r#override: FallbackCommand,
+    pub shutdown: FallbackCommand,
+    pub loiter: FallbackCommand,
+    pub stall: FallbackCommand,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PhaseLimits {
+    pub phase: FlightPhase,
+    pub min_altitude_m: Option<f64>,
+    pub max_bank_deg: Option<f64>,
+    pub max_roll_rate_dps: Option<f64>,
+    pub max_pitch_rate_dps: Option<f64>,
+    pub max_yaw_rate_dps: Option<f64>,
+    pub max_climb_rate_mps: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PhaseTable {
+    pub transitions: heapless::Vec<(FlightPhase, FlightPhase), MAX_PHASE_TRANSITIONS>,
+    pub overrides: heapless::Vec<PhaseLimits, MAX_PHASE_OVERRIDES>,
+}
+
+impl PhaseTable {
+    pub fn allows(&self, from: FlightPhase, to: FlightPhase) -> bool {
+        from == to || self.transitions.contains(&(from, to))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Limits {
+    pub state: StateLimits,
+    pub flow: FlowLimits,
+    pub energy: EnergyLimits,
+    pub guarantees: GuaranteeLimits,
+    pub invariants: InvariantLimits,
+    pub interlock: InterlockLimits,
+    pub deescalation: DeescalationLimits,
+    pub fallbacks: FallbackLimits,
+    pub capabilities: CapabilityLimits,
+    pub phases: PhaseTable,
+    pub transition: Option<TransitionLimits>,
+    pub geofence: Option<Geofence>,
+}
+
+// Invariant and flow limits in force for one tick, after flight-phase
+// overrides and hybrid VTOL transition scheduling.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Envelope {
+    pub invariants: InvariantLimits,
+    pub flow: FlowLimits,
+}
+
+impl Limits {
+    pub fn baked() -> Self {
+        Self {
+            state: StateLimits {
+                frame: Frame::try_from(t::FRAME).expect("specgen bounds the frame length"),
+                position_min_m: t::POSITION_MIN_M,
+                position_max_m: t::POSITION_MAX_M,
+                position_bounds_verdict: t::POSITION_BOUNDS_VERDICT,
+                attitude_limit_deg: t::ATTITUDE_LIMIT_DEG,
+                max_speed_mps: t::MAX_SPEED_MPS,
+            },
+            flow: FlowLimits {
+                max_roll_rate_dps: t::MAX_ROLL_RATE_DPS,
+                max_pitch_rate_dps: t::MAX_PITCH_RATE_DPS,
+                max_yaw_rate_dps: t::MAX_YAW_RATE_DPS,
+                max_climb_rate_mps: t::MAX_CLIMB_RATE_MPS,
+                max_rate_step_dps: t::MAX_RATE_STEP_DPS,
+                max_climb_step_mps: t::MAX_CLIMB_STEP_MPS,
+            },
+            energy: EnergyLimits {
+                min_soc_percent: t::MIN_SOC_PERCENT,
+                reserve_endurance_s: t::RESERVE_ENDURANCE_S,
+                max_power_w: t::MAX_POWER_W,
+            },
+            guarantees: GuaranteeLimits {
+                max_input_age_ms: t::MAX_INPUT_AGE_MS,
+                max_tick_interval_ms: t::MAX_TICK_INTERVAL_MS,
+                deadline_ms: t::DEADLINE_MS,
+                #[cfg(feature = "alloc")]
+                formulas: t::STL_FORMULAS
+                    .iter()
+                    .map(|(id, src)| StlFormula {
+                        id: id.to_string(),
+                        formula: Formula::parse(src).expect("specgen validates STL formulas"),
+                    })
+                    .collect(),
+            },
+            invariants: InvariantLimits {
+                min_altitude_m: t::MIN_ALTITUDE_M,
+                max_bank_deg: t::MAX_BANK_DEG,
+                barrier_gain_per_s: t::BARRIER_GAIN_PER_S,
+            },
+            interlock: InterlockLimits {
+                armed_required: t::ARMED_REQUIRED,
+                fault_latched_shutdown: t::FAULT_LATCHED_SHUTDOWN,
+                reset_authorities: t::RESET_AUTHORITIES
+                    .iter()
+                    .map(|a| Authority::try_from(*a).expect("specgen bounds reset authorities"))
+                    .collect(),
+            },
+            deescalation: DeescalationLimits {
+                clean_ticks: t::DEESCALATION_CLEAN_TICKS,
+                min_dwell_ms: t::DEESCALATION_MIN_DWELL_MS,
+                soc_exit_band_percent: t::SOC_EXIT_BAND_PERCENT,
+                speed_exit_band_mps: t::SPEED_EXIT_BAND_MPS,
+            },
+            fallbacks: FallbackLimits {
+                hold: FallbackCommand {
+                    rates_dps: t::HOLD_RATES_DPS,
+                    climb_mps: t::HOLD_CLIMB_MPS,
+                },
+                r#override: FallbackCommand {
+                    rates_dps: t::OVERRIDE_RATES_DPS,
+                    climb_mps: t::OVERRIDE_CLIMB_MPS,
+                },
+                shutdown: FallbackCommand {
+                    rates_dps: t::SHUTDOWN_RATES_DPS,
+                    climb_mps: t::SHUTDOWN_CLIMB_MPS,
+                },
+                loiter: FallbackCommand {
+                    rates_dps: t::LOITER_RATES_DPS,
+                    climb_mps: t::LOITER_CLIMB_MPS,
+                },
+                stall: FallbackCommand {
+                    rates_dps: t::STALL_RATES_DPS,
+                    climb_mps: t::STALL_CLIMB_MPS,
+                },
+            },
+            capabilities: CapabilityLimits {
+                vtol: p::CAP_VTOL,
+                fixed_wing: p::CAP_FIXED_WING,
+                max_payload_kg: p::CAP_MAX_PAYLOAD_KG,
+                min_airspeed_mps: p::CAP_MIN_AIRSPEED_MPS,
+                max_hover_descent_mps: p::CAP_MAX_HOVER_DESCENT_MPS,
+            },
+            phases: PhaseTable {
+                transitions: heapless::Vec::from_slice(t::PHASE_TRANSITIONS)
+                    .expect("specgen bounds the phase table"),
+                overrides: heapless::Vec::from_slice(t::PHASE_OVERRIDES)
+                    .expect("specgen bounds the phase table"),
+            },
+            transition: p::TRANSITION,
+            geofence: p::GEOFENCE
+                .map(|fence| fence.to_geofence().expect("specgen bounds the geofence")),
+        }
+    }
+
+    // Whether `phase` declares a floor below the profile-merged one.
+    pub fn lowers_floor(&self, phase: FlightPhase) -> bool {
+        self.phases.overrides.iter().any(|o| {
+            o.phase == phase
+                && o.min_altitude_m
+                    .is_some_and(|v| v < self.invariants.min_altitude_m)
+        })
+    }
+
+    // Applies the per-phase invariant and flow overrides declared in
+    // spec/asc/phases.yaml on top of the profile-merged limits, then tightens
+    // them to the airspeed-scheduled hybrid VTOL transition limits. Phase
+    // overrides only tighten, except that a phase the runtime has validated
+    // against the observed state may lower `min_altitude_m`.
+    pub fn envelope(
+        &self,
+        phase: Option<FlightPhase>,
+        phase_validated: bool,
+        airspeed_mps: Option<f64>,
+    ) -> Envelope {
+        let mut envelope = Envelope {
+            invariants: self.invariants,
+            flow: self.flow,
+        };
+        let (invariants, flow) = (&mut envelope.invariants, &mut envelope.flow);
+        if let Some(o) =
+            phase.and_then(|phase| self.phases.overrides.iter().find(|o| o.phase == phase))
+        {
+            let tighten = |base: &mut f64, value: Option<f64>| {
+                if let Some(value) = value {
+                    *base = base.min(value);
+                }
+            };
+            if let Some(min_altitude_m) = o.min_altitude_m {
+                invariants.min_altitude_m = if phase_validated {
+                    min_altitude_m
+                } else {
+                    invariants.min_altitude_m.max(min_altitude_m)
+                };
+            }
+            tighten(&mut invariants.max_bank_deg, o.max_bank_deg);
+            tighten(&mut flow.max_roll_rate_dps, o.max_roll_rate_dps);
+            tighten(&mut flow.max_pitch_rate_dps, o.max_pitch_rate_dps);
+            tighten(&mut flow.max_yaw_rate_dps, o.max_yaw_rate_dps);
+            tighten(&mut flow.max_climb_rate_mps, o.max_climb_rate_mps);
+        }
+        // Without an airspeed the vehicle is assumed to hover, the tighter set.
+        if let Some(mode) = self
+            .transition
+            .map(|t| airspeed_mps.map_or(t.hover, |airspeed_mps| t.blend(airspeed_mps)))
+        {
+            let tighten = |base: &mut f64, value: f64| *base = base.min(value);
+            tighten(&mut invariants.max_bank_deg, mode.max_bank_deg);
+            tighten(&mut flow.max_roll_rate_dps, mode.max_roll_rate_dps);
+            tighten(&mut flow.max_pitch_rate_dps, mode.max_pitch_rate_dps);
+            tighten(&mut flow.max_yaw_rate_dps, mode.max_yaw_rate_dps);
+            tighten(&mut flow.max_climb_rate_mps, mode.max_climb_rate_mps);
+        }
+        envelope
+    }
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self::baked()
+    }
+}
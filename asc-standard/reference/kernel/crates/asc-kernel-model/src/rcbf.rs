@@ -0,0 +1,56 @@
+use asc_types::model::ObservedState;
+
+use crate::limits::InvariantLimits;
+
+// Reciprocal control barrier on the altitude and bank invariants: with
+// h = margin to the invariant, an intent is admissible while dh/dt >= -gain * h,
+// so the allowed approach rate shrinks to zero at the boundary.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Admissible {
+    pub min_climb_mps: f64,
+    pub min_roll_rate_dps: f64,
+    pub max_roll_rate_dps: f64,
+}
+
+impl Admissible {
+    pub fn from_state(state: &ObservedState, inv: &InvariantLimits) -> Self {
+        let gain = inv.barrier_gain_per_s;
+        Self {
+            min_climb_mps: -gain * (state.altitude_m() - inv.min_altitude_m),
+            min_roll_rate_dps: -gain * (inv.max_bank_deg + state.bank_deg),
+            max_roll_rate_dps: gain * (inv.max_bank_deg - state.bank_deg),
+        }
+    }
+
+    // The barrier constraint nearest to (or furthest past) its bound, as
+    // (measured, bound, margin).
+    pub fn binding(&self, rates_dps: [f64; 3], climb_mps: f64) -> (f64, f64, f64) {
+        [
+            (
+                climb_mps,
+                self.min_climb_mps,
+                climb_mps - self.min_climb_mps,
+            ),
+            (
+                rates_dps[0],
+                self.min_roll_rate_dps,
+                rates_dps[0] - self.min_roll_rate_dps,
+            ),
+            (
+                rates_dps[0],
+                self.max_roll_rate_dps,
+                self.max_roll_rate_dps - rates_dps[0],
+            ),
+        ]
+        .into_iter()
+        .min_by(|a, b| a.2.total_cmp(&b.2))
+        .expect("barrier has constraints")
+    }
+
+    pub fn project(&self, mut rates_dps: [f64; 3], climb_mps: f64) -> ([f64; 3], f64) {
+        rates_dps[0] = rates_dps[0]
+            .max(self.min_roll_rate_dps)
+            .min(self.max_roll_rate_dps);
+        (rates_dps, climb_mps.max(self.min_climb_mps))
+    }
+}
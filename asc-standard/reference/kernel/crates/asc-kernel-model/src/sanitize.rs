@@ -0,0 +1,33 @@
+use asc_types::model::{ConstrainedCommand, KernelInput};
+
+pub fn input_is_finite(input: &KernelInput) -> bool {
+    let state = &input.state;
+    let intent = &input.intent;
+    state.position_m.iter().all(|v| v.is_finite())
+        && state.velocity_mps.is_finite()
+        && state.airspeed_mps.is_none_or(f64::is_finite)
+        && state.bank_deg.is_finite()
+        && state.pitch_deg.is_finite()
+        && state.soc_percent.is_finite()
+        && state.power_w.is_finite()
+        && state.battery_capacity_wh.is_finite()
+        && state.payload_kg.is_finite()
+        && intent.desired_rates_dps.iter().all(|v| v.is_finite())
+        && intent.desired_climb_mps.is_finite()
+}
+
+pub fn finite_command(command: ConstrainedCommand) -> ConstrainedCommand {
+    ConstrainedCommand {
+        applied_rates_dps: command.applied_rates_dps.map(finite_or_zero),
+        applied_climb_mps: finite_or_zero(command.applied_climb_mps),
+        shutdown: command.shutdown,
+    }
+}
+
+fn finite_or_zero(value: f64) -> f64 {
+    if value.is_finite() {
+        value
+    } else {
+        0.0
+    }
+}
@@ -0,0 +1,17 @@
+use asc_types::{model::ConstrainedCommand, Verdict};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HeldVerdict {
+    pub verdict: Verdict,
+    pub since_ts_ms: u64,
+    pub clean_ticks: u32,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct KernelState {
+    pub held: Option<HeldVerdict>,
+    pub soc_floor_engaged: bool,
+    pub overspeed_engaged: bool,
+    pub last_command: Option<ConstrainedCommand>,
+}
@@ -0,0 +1,512 @@
+use alloc::{
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use asc_types::model::KernelInput;
+use core::fmt;
+use serde::{Deserialize, Serialize};
+
+pub const MAX_STL_HORIZON_MS: u64 = 60_000;
+pub const STL_EVALUATIONS_PER_MS: u64 = 10_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Signal {
+    Soc,
+    InputAge,
+    Speed,
+    Altitude,
+    Bank,
+    Pitch,
+    Power,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Cmp {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Formula {
+    Predicate {
+        signal: Signal,
+        cmp: Cmp,
+        threshold: f64,
+    },
+    Not(Box<Formula>),
+    And(Box<Formula>, Box<Formula>),
+    Or(Box<Formula>, Box<Formula>),
+    Always {
+        from_ms: u64,
+        to_ms: u64,
+        body: Box<Formula>,
+    },
+    Eventually {
+        from_ms: u64,
+        to_ms: u64,
+        body: Box<Formula>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StlFormula {
+    pub id: String,
+    pub formula: Formula,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct StlSample {
+    pub ts_ms: u64,
+    pub soc: f64,
+    pub input_age: f64,
+    pub speed: f64,
+    pub altitude: f64,
+    pub bank: f64,
+    pub pitch: f64,
+    pub power: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StlParseError(pub String);
+
+impl fmt::Display for StlParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl core::error::Error for StlParseError {}
+
+impl Signal {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "soc" => Self::Soc,
+            "input_age" => Self::InputAge,
+            "speed" => Self::Speed,
+            "altitude" => Self::Altitude,
+            "bank" => Self::Bank,
+            "pitch" => Self::Pitch,
+            "power" => Self::Power,
+            _ => return None,
+        })
+    }
+
+    fn value(self, sample: &StlSample) -> f64 {
+        match self {
+            Self::Soc => sample.soc,
+            Self::InputAge => sample.input_age,
+            Self::Speed => sample.speed,
+            Self::Altitude => sample.altitude,
+            Self::Bank => sample.bank,
+            Self::Pitch => sample.pitch,
+            Self::Power => sample.power,
+        }
+    }
+}
+
+impl StlSample {
+    pub fn from_input(input: &KernelInput) -> Self {
+        Self {
+            ts_ms: input.tick.ts_ms,
+            soc: input.state.soc_percent,
+            input_age: input.state.input_age_ms as f64,
+            speed: input.state.velocity_mps,
+            altitude: input.state.altitude_m(),
+            bank: input.state.bank_deg,
+            pitch: input.state.pitch_deg,
+            power: input.state.power_w,
+        }
+    }
+}
+
+impl Formula {
+    pub fn parse(src: &str) -> Result<Self, StlParseError> {
+        let mut parser = Parser {
+            tokens: tokenize(src)?,
+            pos: 0,
+        };
+        let formula = parser.or()?;
+        if let Some(token) = parser.tokens.get(parser.pos) {
+            return Err(StlParseError(format!("unexpected trailing {token:?}")));
+        }
+        Ok(formula)
+    }
+
+    // Saturates, so an oversized formula is rejected by `validate` rather than
+    // wrapping to a short horizon.
+    pub fn horizon_ms(&self) -> u64 {
+        match self {
+            Self::Predicate { .. } => 0,
+            Self::Not(body) => body.horizon_ms(),
+            Self::And(lhs, rhs) | Self::Or(lhs, rhs) => lhs.horizon_ms().max(rhs.horizon_ms()),
+            Self::Always { to_ms, body, .. } | Self::Eventually { to_ms, body, .. } => {
+                to_ms.saturating_add(body.horizon_ms())
+            }
+        }
+    }
+
+    // Number of subformulas, and so the length of each row `eval` records.
+    pub fn node_count(&self) -> usize {
+        match self {
+            Self::Predicate { .. } => 1,
+            Self::Not(body) | Self::Always { body, .. } | Self::Eventually { body, .. } => {
+                1 + body.node_count()
+            }
+            Self::And(lhs, rhs) | Self::Or(lhs, rhs) => 1 + lhs.node_count() + rhs.node_count(),
+        }
+    }
+
+    // Worst-case subformula evaluations per tick at `control_hz`: every
+    // temporal operator scans the recorded ticks in its interval once.
+    pub fn cost(&self, control_hz: u64) -> u64 {
+        match self {
+            Self::Predicate { .. } => 1,
+            Self::Not(body) => body.cost(control_hz).saturating_add(1),
+            Self::And(lhs, rhs) | Self::Or(lhs, rhs) => lhs
+                .cost(control_hz)
+                .saturating_add(rhs.cost(control_hz))
+                .saturating_add(1),
+            Self::Always {
+                from_ms,
+                to_ms,
+                body,
+            }
+            | Self::Eventually {
+                from_ms,
+                to_ms,
+                body,
+            } => ((to_ms - from_ms).saturating_mul(control_hz) / 1000 + 1)
+                .saturating_add(body.cost(control_hz)),
+        }
+    }
+
+    // Three-valued bounded-past semantics. `samples` ends with the tick being
+    // evaluated and `rows` holds the value every subformula took at each
+    // earlier tick, in pre-order; the values for the new tick are appended to
+    // `row`. A past value never changes once recorded, so each tick costs
+    // `cost` rather than growing with the nesting depth.
+    //
+    // A temporal operator is undecided (`None`) until the history since
+    // `started_ts_ms` spans its whole interval, unless a recorded tick already
+    // settles it. Connectives propagate undecided values like Kleene logic.
+    pub fn eval(
+        &self,
+        samples: &[StlSample],
+        rows: &[Vec<Option<bool>>],
+        started_ts_ms: u64,
+        row: &mut Vec<Option<bool>>,
+    ) -> Option<bool> {
+        let at = row.len();
+        row.push(None);
+        let sample = &samples[samples.len() - 1];
+        let value = match self {
+            Self::Predicate {
+                signal,
+                cmp,
+                threshold,
+            } => {
+                let value = signal.value(sample);
+                Some(match cmp {
+                    Cmp::Lt => value < *threshold,
+                    Cmp::Le => value <= *threshold,
+                    Cmp::Gt => value > *threshold,
+                    Cmp::Ge => value >= *threshold,
+                })
+            }
+            Self::Not(body) => body.eval(samples, rows, started_ts_ms, row).map(|v| !v),
+            Self::And(lhs, rhs) => {
+                let lhs = lhs.eval(samples, rows, started_ts_ms, row);
+                let rhs = rhs.eval(samples, rows, started_ts_ms, row);
+                match (lhs, rhs) {
+                    (Some(false), _) | (_, Some(false)) => Some(false),
+                    (Some(true), Some(true)) => Some(true),
+                    _ => None,
+                }
+            }
+            Self::Or(lhs, rhs) => {
+                let lhs = lhs.eval(samples, rows, started_ts_ms, row);
+                let rhs = rhs.eval(samples, rows, started_ts_ms, row);
+                match (lhs, rhs) {
+                    (Some(true), _) | (_, Some(true)) => Some(true),
+                    (Some(false), Some(false)) => Some(false),
+                    _ => None,
+                }
+            }
+            Self::Always {
+                from_ms,
+                to_ms,
+                body,
+            } => {
+                let now = body.eval(samples, rows, started_ts_ms, row);
+                let spanned = sample.ts_ms - started_ts_ms >= *to_ms;
+                window(
+                    samples,
+                    rows,
+                    now,
+                    at + 1,
+                    (*from_ms, *to_ms),
+                    false,
+                    spanned,
+                )
+            }
+            Self::Eventually {
+                from_ms,
+                to_ms,
+                body,
+            } => {
+                let now = body.eval(samples, rows, started_ts_ms, row);
+                let spanned = sample.ts_ms - started_ts_ms >= *to_ms;
+                window(
+                    samples,
+                    rows,
+                    now,
+                    at + 1,
+                    (*from_ms, *to_ms),
+                    true,
+                    spanned,
+                )
+            }
+        };
+        row[at] = value;
+        value
+    }
+}
+
+// Folds the values the subformula at `body` took over the ticks between
+// `from_ms` and `to_ms` ago. `settles` is the value that decides the operator
+// on its own: `true` for `eventually`, `false` for `always`.
+fn window(
+    samples: &[StlSample],
+    rows: &[Vec<Option<bool>>],
+    now: Option<bool>,
+    body: usize,
+    (from_ms, to_ms): (u64, u64),
+    settles: bool,
+    spanned: bool,
+) -> Option<bool> {
+    let now_ms = samples[samples.len() - 1].ts_ms;
+    let past = samples
+        .iter()
+        .zip(rows)
+        .map(|(s, row)| (s.ts_ms, row[body]));
+    let mut undecided = false;
+    for (ts_ms, value) in past.chain([(now_ms, now)]) {
+        let age_ms = now_ms - ts_ms;
+        if age_ms < from_ms || age_ms > to_ms {
+            continue;
+        }
+        match value {
+            Some(v) if v == settles => return Some(settles),
+            Some(_) => {}
+            None => undecided = true,
+        }
+    }
+    (!undecided && spanned).then_some(!settles)
+}
+
+// Rejects formulas the monitor could not evaluate within the tick deadline:
+// horizons over `MAX_STL_HORIZON_MS`, or more than
+// `STL_EVALUATIONS_PER_MS * deadline_ms` evaluations per tick at `control_hz`.
+pub fn validate(
+    formulas: &[StlFormula],
+    control_hz: u64,
+    deadline_ms: u64,
+) -> Result<(), StlParseError> {
+    let mut cost = 0u64;
+    for f in formulas {
+        let horizon_ms = f.formula.horizon_ms();
+        if horizon_ms > MAX_STL_HORIZON_MS {
+            return Err(StlParseError(format!(
+                "formula {} horizon {horizon_ms}ms exceeds {MAX_STL_HORIZON_MS}ms",
+                f.id
+            )));
+        }
+        cost = cost.saturating_add(f.formula.cost(control_hz));
+    }
+    let budget = deadline_ms.saturating_mul(STL_EVALUATIONS_PER_MS);
+    if cost > budget {
+        return Err(StlParseError(format!(
+            "formulas need {cost} evaluations per tick at {control_hz} Hz, over the {budget} allowed in {deadline_ms}ms"
+        )));
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Number(f64),
+    Sym(&'static str),
+}
+
+fn tokenize(src: &str) -> Result<Vec<Token>, StlParseError> {
+    const SYMBOLS: [&str; 12] = [
+        "&&", "||", "<=", ">=", "<", ">", "!", "(", ")", "[", "]", ",",
+    ];
+    let mut tokens = Vec::new();
+    let mut rest = src.trim_start();
+    while !rest.is_empty() {
+        if let Some(sym) = SYMBOLS.iter().find(|sym| rest.starts_with(**sym)) {
+            tokens.push(Token::Sym(sym));
+            rest = &rest[sym.len()..];
+        } else if rest.starts_with(|c: char| c.is_ascii_digit() || c == '-' || c == '.') {
+            let end = rest[1..]
+                .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+                .map_or(rest.len(), |i| i + 1);
+            let number = rest[..end]
+                .parse()
+                .map_err(|_| StlParseError(format!("invalid number {}", &rest[..end])))?;
+            tokens.push(Token::Number(number));
+            rest = &rest[end..];
+        } else if rest.starts_with(|c: char| c.is_ascii_lowercase() || c == '_') {
+            let end = rest
+                .find(|c: char| !(c.is_ascii_lowercase() || c == '_'))
+                .unwrap_or(rest.len());
+            tokens.push(Token::Ident(rest[..end].to_string()));
+            rest = &rest[end..];
+        } else {
+            return Err(StlParseError(format!("unexpected character in {rest:?}")));
+        }
+        rest = rest.trim_start();
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn eat(&mut self, sym: &str) -> bool {
+        if matches!(self.tokens.get(self.pos), Some(Token::Sym(s)) if *s == sym) {
+            self.pos += 1;
+            return true;
+        }
+        false
+    }
+
+    fn expect(&mut self, sym: &str) -> Result<(), StlParseError> {
+        if self.eat(sym) {
+            return Ok(());
+        }
+        Err(StlParseError(format!(
+            "expected '{sym}', found {:?}",
+            self.tokens.get(self.pos)
+        )))
+    }
+
+    fn or(&mut self) -> Result<Formula, StlParseError> {
+        let mut lhs = self.and()?;
+        while self.eat("||") {
+            lhs = Formula::Or(Box::new(lhs), Box::new(self.and()?));
+        }
+        Ok(lhs)
+    }
+
+    fn and(&mut self) -> Result<Formula, StlParseError> {
+        let mut lhs = self.unary()?;
+        while self.eat("&&") {
+            lhs = Formula::And(Box::new(lhs), Box::new(self.unary()?));
+        }
+        Ok(lhs)
+    }
+
+    fn unary(&mut self) -> Result<Formula, StlParseError> {
+        if self.eat("!") {
+            return Ok(Formula::Not(Box::new(self.unary()?)));
+        }
+        if self.eat("(") {
+            let inner = self.or()?;
+            self.expect(")")?;
+            return Ok(inner);
+        }
+        match self.next() {
+            Some(Token::Ident(name)) if name == "always" || name == "eventually" => {
+                self.expect("[")?;
+                let from_ms = self.duration_ms()?;
+                self.expect(",")?;
+                let to_ms = self.duration_ms()?;
+                self.expect("]")?;
+                if from_ms > to_ms {
+                    return Err(StlParseError(format!(
+                        "{name} interval [{from_ms}ms, {to_ms}ms] is empty"
+                    )));
+                }
+                self.expect("(")?;
+                let body = Box::new(self.or()?);
+                self.expect(")")?;
+                Ok(if name == "always" {
+                    Formula::Always {
+                        from_ms,
+                        to_ms,
+                        body,
+                    }
+                } else {
+                    Formula::Eventually {
+                        from_ms,
+                        to_ms,
+                        body,
+                    }
+                })
+            }
+            Some(Token::Ident(name)) => {
+                let signal = Signal::from_name(&name)
+                    .ok_or_else(|| StlParseError(format!("unknown signal {name}")))?;
+                let cmp = match self.next() {
+                    Some(Token::Sym("<")) => Cmp::Lt,
+                    Some(Token::Sym("<=")) => Cmp::Le,
+                    Some(Token::Sym(">")) => Cmp::Gt,
+                    Some(Token::Sym(">=")) => Cmp::Ge,
+                    other => {
+                        return Err(StlParseError(format!(
+                            "expected comparison after {name}, found {other:?}"
+                        )))
+                    }
+                };
+                match self.next() {
+                    Some(Token::Number(threshold)) => Ok(Formula::Predicate {
+                        signal,
+                        cmp,
+                        threshold,
+                    }),
+                    other => Err(StlParseError(format!(
+                        "expected threshold after {name}, found {other:?}"
+                    ))),
+                }
+            }
+            other => Err(StlParseError(format!("unexpected {other:?}"))),
+        }
+    }
+
+    fn duration_ms(&mut self) -> Result<u64, StlParseError> {
+        let value = match self.next() {
+            Some(Token::Number(value)) if value >= 0.0 => value,
+            other => return Err(StlParseError(format!("expected duration, found {other:?}"))),
+        };
+        // Zero is the same in every unit, so it may be written bare.
+        if value == 0.0 && !matches!(self.tokens.get(self.pos), Some(Token::Ident(_))) {
+            return Ok(0);
+        }
+        let scale = match self.next() {
+            Some(Token::Ident(unit)) if unit == "ms" => 1.0,
+            Some(Token::Ident(unit)) if unit == "s" => 1000.0,
+            other => {
+                return Err(StlParseError(format!(
+                    "expected duration unit ms or s, found {other:?}"
+                )))
+            }
+        };
+        Ok(libm::round(value * scale) as u64)
+    }
+}
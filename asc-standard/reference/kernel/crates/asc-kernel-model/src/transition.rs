@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VtolMode {
+    Hover,
+    Transition,
+    Forward,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ModeLimits {
+    pub max_bank_deg: f64,
+    pub max_roll_rate_dps: f64,
+    pub max_pitch_rate_dps: f64,
+    pub max_yaw_rate_dps: f64,
+    pub max_climb_rate_mps: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TransitionLimits {
+    pub hover_airspeed_mps: f64,
+    pub forward_airspeed_mps: f64,
+    pub hover: ModeLimits,
+    pub forward: ModeLimits,
+}
+
+impl TransitionLimits {
+    pub fn mode(&self, airspeed_mps: f64) -> VtolMode {
+        if airspeed_mps >= self.forward_airspeed_mps {
+            VtolMode::Forward
+        } else if airspeed_mps > self.hover_airspeed_mps {
+            VtolMode::Transition
+        } else {
+            VtolMode::Hover
+        }
+    }
+
+    // Limits are scheduled linearly on airspeed between the hover and forward
+    // sets. A non-finite airspeed is treated as hover, the tighter set.
+    pub fn blend(&self, airspeed_mps: f64) -> ModeLimits {
+        let span = self.forward_airspeed_mps - self.hover_airspeed_mps;
+        let s = ((airspeed_mps - self.hover_airspeed_mps) / span).clamp(0.0, 1.0);
+        let s = if s.is_nan() { 0.0 } else { s };
+        let lerp = |hover: f64, forward: f64| hover + (forward - hover) * s;
+        let (h, f) = (&self.hover, &self.forward);
+        ModeLimits {
+            max_bank_deg: lerp(h.max_bank_deg, f.max_bank_deg),
+            max_roll_rate_dps: lerp(h.max_roll_rate_dps, f.max_roll_rate_dps),
+            max_pitch_rate_dps: lerp(h.max_pitch_rate_dps, f.max_pitch_rate_dps),
+            max_yaw_rate_dps: lerp(h.max_yaw_rate_dps, f.max_yaw_rate_dps),
+            max_climb_rate_mps: lerp(h.max_climb_rate_mps, f.max_climb_rate_mps),
+        }
+    }
+}
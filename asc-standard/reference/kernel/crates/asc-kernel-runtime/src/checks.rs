@@ -0,0 +1,80 @@
+use anyhow::{bail, Result};
+use asc_contract::ProgramChecks;
+use asc_kernel_model::{checks::MAX_PROGRAM_CHECKS, Check, CheckParams};
+use sha2::{Digest, Sha256};
+
+// A program check implementation with the parameters of its declaration.
+pub struct ProgramCheck {
+    pub check: Box<dyn Check>,
+    pub params: CheckParams,
+}
+
+// Orders program check implementations by their declaration in the contract,
+// so evaluation order and `CheckId::Program` indices follow the fingerprinted
+// spec rather than registration order.
+pub fn program_checks(
+    declared: &ProgramChecks,
+    mut implementations: Vec<Box<dyn Check>>,
+) -> Result<Vec<ProgramCheck>> {
+    if declared.checks.len() > MAX_PROGRAM_CHECKS {
+        bail!("checks exceeds the kernel capacity of {MAX_PROGRAM_CHECKS} entries")
+    }
+    let mut ordered = Vec::with_capacity(declared.checks.len());
+    for spec in &declared.checks {
+        let Some(index) = implementations.iter().position(|c| c.name() == spec.id) else {
+            bail!("program check {} has no registered implementation", spec.id)
+        };
+        let check = implementations.remove(index);
+        if check.reason() != spec.reason
+            || check.severity() != spec.severity
+            || check.verdict() != spec.verdict
+        {
+            bail!(
+                "program check {} declares {:?}/{:?}/{:?} but the contract declares {:?}/{:?}/{:?}",
+                spec.id,
+                check.reason(),
+                check.severity(),
+                check.verdict(),
+                spec.reason,
+                spec.severity,
+                spec.verdict
+            )
+        }
+        let mut params = CheckParams::new();
+        for (name, value) in &spec.params {
+            if params.insert(name, *value).is_err() {
+                bail!(
+                    "program check {} parameter {name} exceeds the kernel capacity",
+                    spec.id
+                )
+            }
+        }
+        ordered.push(ProgramCheck { check, params });
+    }
+    if let Some(extra) = implementations.first() {
+        bail!(
+            "program check {} is not declared in spec/asc/checks.yaml",
+            extra.name()
+        )
+    }
+    Ok(ordered)
+}
+
+// The contract fingerprint extended with the name and version of each program
+// check implementation, in declaration order, so a decision record identifies
+// the code behind its program outcomes. Without program checks it is the
+// contract fingerprint itself.
+pub fn checks_fingerprint(contract_fingerprint: &str, checks: &[ProgramCheck]) -> String {
+    if checks.is_empty() {
+        return contract_fingerprint.to_string();
+    }
+    let mut hasher = Sha256::new();
+    hasher.update(contract_fingerprint);
+    for program in checks {
+        for part in [program.check.name(), program.check.version()] {
+            hasher.update(part);
+            hasher.update([0]);
+        }
+    }
+    hex::encode(hasher.finalize())
+}
@@ -1,3 +1,8 @@
+pub mod checks;
+pub mod limits;
 pub mod runtime;
+pub mod stl;
 
+pub use checks::{checks_fingerprint, program_checks, ProgramCheck};
+pub use limits::limits_from_contract;
 pub use runtime::Runtime;
@@ -0,0 +1,202 @@
+use anyhow::{anyhow, Context, Result};
+use asc_contract::{ContractBundle, FenceShapeSpec, FenceZoneSpec, GeofenceSpec, ModeLimitsSpec};
+use asc_kernel_model::{
+    geofence::{FenceShape, FenceZone, Geofence},
+    limits::{
+        CapabilityLimits, DeescalationLimits, EnergyLimits, FallbackCommand, FallbackLimits,
+        FlowLimits, GuaranteeLimits, InterlockLimits, InvariantLimits, Limits, PhaseLimits,
+        PhaseTable, StateLimits,
+    },
+    stl::{self, Formula, StlFormula},
+    transition::{ModeLimits, TransitionLimits},
+};
+
+pub fn limits_from_contract(bundle: &ContractBundle) -> Result<Limits> {
+    let formulas = bundle
+        .guarantees
+        .formulas
+        .iter()
+        .map(|f| {
+            Ok(StlFormula {
+                id: f.id.clone(),
+                formula: Formula::parse(&f.formula)
+                    .with_context(|| format!("guarantee formula {} is invalid", f.id))?,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    stl::validate(
+        &formulas,
+        bundle.profile.timing.control_hz,
+        bundle.guarantees.deadline_ms,
+    )
+    .context("guarantee formulas exceed the monitor budget")?;
+    let reset_authorities = bundle
+        .interlock
+        .reset_authorities
+        .iter()
+        .map(|a| bounded_str(a, "interlock.reset_authorities"))
+        .collect::<Result<Vec<_>>>()?;
+    let reset_authorities = bounded(&reset_authorities, "interlock.reset_authorities")?;
+
+    Ok(Limits {
+        state: StateLimits {
+            frame: bounded_str(&bundle.state.frame, "state.frame")?,
+            position_min_m: bundle.state.position_bounds_m.min,
+            position_max_m: bundle.state.position_bounds_m.max,
+            position_bounds_verdict: bundle.state.position_bounds_verdict,
+            attitude_limit_deg: bundle.state.attitude_limit_deg,
+            max_speed_mps: bundle.state.max_speed_mps,
+        },
+        flow: FlowLimits {
+            max_roll_rate_dps: bundle.flow.max_roll_rate_dps,
+            max_pitch_rate_dps: bundle.flow.max_pitch_rate_dps,
+            max_yaw_rate_dps: bundle.flow.max_yaw_rate_dps,
+            max_climb_rate_mps: bundle.flow.max_climb_rate_mps,
+            max_rate_step_dps: bundle.flow.max_rate_step_dps,
+            max_climb_step_mps: bundle.flow.max_climb_step_mps,
+        },
+        energy: EnergyLimits {
+            min_soc_percent: bundle.energy.min_soc_percent,
+            reserve_endurance_s: bundle.energy.reserve_endurance_s,
+            max_power_w: bundle.energy.max_power_w,
+        },
+        guarantees: GuaranteeLimits {
+            max_input_age_ms: bundle.guarantees.max_input_age_ms,
+            max_tick_interval_ms: bundle.guarantees.max_tick_interval_ms,
+            deadline_ms: bundle.guarantees.deadline_ms,
+            formulas,
+        },
+        invariants: InvariantLimits {
+            min_altitude_m: bundle.invariants.min_altitude_m,
+            max_bank_deg: bundle.invariants.max_bank_deg,
+            barrier_gain_per_s: bundle.invariants.barrier_gain_per_s,
+        },
+        interlock: InterlockLimits {
+            armed_required: bundle.interlock.armed_required,
+            fault_latched_shutdown: bundle.interlock.fault_latched_shutdown,
+            reset_authorities,
+        },
+        deescalation: DeescalationLimits {
+            clean_ticks: bundle.deescalation.clean_ticks,
+            min_dwell_ms: bundle.deescalation.min_dwell_ms,
+            soc_exit_band_percent: bundle.deescalation.exit_bands.soc_percent,
+            speed_exit_band_mps: bundle.deescalation.exit_bands.speed_mps,
+        },
+        fallbacks: FallbackLimits {
+            hold: fallback_command(&bundle.fallbacks.hold),
+            r#override: fallback_command(&bundle.fallbacks.r#override),
+            shutdown: fallback_command(&bundle.fallbacks.shutdown),
+            loiter: fallback_command(&bundle.fallbacks.loiter),
+            stall: fallback_command(&bundle.fallbacks.stall),
+        },
+        capabilities: CapabilityLimits {
+            vtol: bundle.profile.capabilities.vtol,
+            fixed_wing: bundle.profile.capabilities.fixed_wing,
+            max_payload_kg: bundle.profile.capabilities.max_payload_kg,
+            min_airspeed_mps: bundle.profile.capabilities.min_airspeed_mps,
+            max_hover_descent_mps: bundle.profile.capabilities.max_hover_descent_mps,
+        },
+        phases: PhaseTable {
+            transitions: bounded(
+                &bundle
+                    .phases
+                    .transitions
+                    .iter()
+                    .flat_map(|(from, targets)| targets.iter().map(|to| (*from, *to)))
+                    .collect::<Vec<_>>(),
+                "phases.transitions",
+            )?,
+            overrides: bundle
+                .phases
+                .overrides
+                .iter()
+                .map(|(phase, o)| {
+                    let inv = o.invariants.as_ref();
+                    let flow = o.flow.as_ref();
+                    PhaseLimits {
+                        phase: *phase,
+                        min_altitude_m: inv.and_then(|i| i.min_altitude_m),
+                        max_bank_deg: inv.and_then(|i| i.max_bank_deg),
+                        max_roll_rate_dps: flow.and_then(|f| f.max_roll_rate_dps),
+                        max_pitch_rate_dps: flow.and_then(|f| f.max_pitch_rate_dps),
+                        max_yaw_rate_dps: flow.and_then(|f| f.max_yaw_rate_dps),
+                        max_climb_rate_mps: flow.and_then(|f| f.max_climb_rate_mps),
+                    }
+                })
+                .collect(),
+        },
+        transition: bundle
+            .profile
+            .transition
+            .as_ref()
+            .map(|transition| TransitionLimits {
+                hover_airspeed_mps: transition.hover_airspeed_mps,
+                forward_airspeed_mps: bundle
+                    .profile
+                    .capabilities
+                    .min_airspeed_mps
+                    .unwrap_or_default(),
+                hover: mode_limits(&transition.hover),
+                forward: mode_limits(&transition.forward),
+            }),
+        geofence: bundle.geofence.as_ref().map(geofence).transpose()?,
+    })
+}
+
+// The kernel model stores contract data in fixed-capacity collections so that
+// it can run without an allocator.
+fn bounded<T: Clone, const N: usize>(items: &[T], what: &str) -> Result<heapless::Vec<T, N>> {
+    heapless::Vec::from_slice(items)
+        .map_err(|()| anyhow!("{what} exceeds the kernel capacity of {N} entries"))
+}
+
+fn bounded_str<const N: usize>(value: &str, what: &str) -> Result<heapless::String<N>> {
+    heapless::String::try_from(value)
+        .map_err(|()| anyhow!("{what} exceeds the kernel capacity of {N} bytes"))
+}
+
+fn geofence(fence: &GeofenceSpec) -> Result<Geofence> {
+    let exclusions = fence
+        .exclusions
+        .iter()
+        .map(fence_zone)
+        .collect::<Result<Vec<_>>>()?;
+    Ok(Geofence {
+        inclusion: fence_zone(&fence.inclusion)?,
+        exclusions: bounded(&exclusions, "geofence.exclusions")?,
+    })
+}
+
+fn fence_zone(zone: &FenceZoneSpec) -> Result<FenceZone> {
+    Ok(FenceZone {
+        id: bounded_str(&zone.id, "geofence zone id")?,
+        shape: match &zone.shape {
+            FenceShapeSpec::Polygon { vertices_m } => FenceShape::Polygon {
+                vertices_m: bounded(vertices_m, "geofence polygon vertices")?,
+            },
+            FenceShapeSpec::Cylinder { center_m, radius_m } => FenceShape::Cylinder {
+                center_m: *center_m,
+                radius_m: *radius_m,
+            },
+        },
+        floor_m: zone.floor_m,
+        ceiling_m: zone.ceiling_m,
+    })
+}
+
+fn fallback_command(spec: &asc_contract::FallbackCommand) -> FallbackCommand {
+    FallbackCommand {
+        rates_dps: spec.rates_dps,
+        climb_mps: spec.climb_mps,
+    }
+}
+
+fn mode_limits(spec: &ModeLimitsSpec) -> ModeLimits {
+    ModeLimits {
+        max_bank_deg: spec.max_bank_deg,
+        max_roll_rate_dps: spec.max_roll_rate_dps,
+        max_pitch_rate_dps: spec.max_pitch_rate_dps,
+        max_yaw_rate_dps: spec.max_yaw_rate_dps,
+        max_climb_rate_mps: spec.max_climb_rate_mps,
+    }
+}
@@ -1,43 +1,455 @@
-use anyhow::Result;
+use anyhow::{bail, Result};
 use asc_contract::load_contract;
-use asc_kernel_model::constrain;
-use asc_logging::EventLog;
-use asc_types::model::{KernelInput, KernelOutput};
+use asc_kernel_model::{
+    constrain, context::MAX_TICK_ANOMALIES, Check, CheckRegistry, KernelState, Limits, TickContext,
+};
+use asc_logging::{Evaluation, EventLog, EventPayload, EventRecord, RuntimeEvent, RuntimeSnapshot};
+use asc_types::{
+    model::{
+        CheckId, Fingerprint, FlightPhase, KernelInput, KernelOutput, TickAnomaly,
+        FINGERPRINT_CAPACITY,
+    },
+    Verdict,
+};
 use std::path::Path;
 
+use crate::{
+    checks::{checks_fingerprint, program_checks, ProgramCheck},
+    limits::limits_from_contract,
+    stl::StlMonitor,
+};
+
 pub struct Runtime {
-    contract_fingerprint: String,
+    contract_fingerprint: Fingerprint,
+    limits: Limits,
+    checks: Vec<ProgramCheck>,
     last_tick_ts_ms: Option<u64>,
+    last_seq: Option<u64>,
+    shutdown_latched: bool,
+    phase: Option<FlightPhase>,
+    phase_validated: bool,
+    kernel_state: KernelState,
+    stl: StlMonitor,
     pub log: EventLog,
 }
 
 impl Runtime {
-    pub fn new(contract_fingerprint: String) -> Self {
-        Self {
+    pub fn new(contract_fingerprint: String) -> Result<Self> {
+        Self::with_limits(contract_fingerprint, Limits::baked())
+    }
+
+    // KernelOutput carries the contract fingerprint in fixed-size storage; hex
+    // SHA-256 digests fit, and anything longer is rejected.
+    pub fn with_limits(contract_fingerprint: String, limits: Limits) -> Result<Self> {
+        let Ok(contract_fingerprint) = Fingerprint::try_from(contract_fingerprint.as_str()) else {
+            bail!("contract fingerprint exceeds {FINGERPRINT_CAPACITY} bytes")
+        };
+        Ok(Self {
             contract_fingerprint,
+            limits,
+            checks: Vec::new(),
             last_tick_ts_ms: None,
+            last_seq: None,
+            shutdown_latched: false,
+            phase: None,
+            phase_validated: false,
+            kernel_state: KernelState::default(),
+            stl: StlMonitor::default(),
             log: EventLog::default(),
-        }
+        })
     }
 
     pub fn from_repo(repo_root: &Path, profile_name: &str) -> Result<Self> {
+        Self::from_repo_with_checks(repo_root, profile_name, Vec::new())
+    }
+
+    // Every program check declared in spec/asc/checks.yaml needs a matching
+    // implementation, and every implementation needs a declaration.
+    pub fn from_repo_with_checks(
+        repo_root: &Path,
+        profile_name: &str,
+        checks: Vec<Box<dyn Check>>,
+    ) -> Result<Self> {
         let bundle = load_contract(repo_root, profile_name)?;
-        Ok(Self::new(bundle.fingerprint))
+        let limits = limits_from_contract(&bundle)?;
+        let checks = program_checks(&bundle.checks, checks)?;
+        let fingerprint = checks_fingerprint(&bundle.fingerprint, &checks);
+        let mut runtime = Self::with_limits(fingerprint, limits)?;
+        runtime.checks = checks;
+        Ok(runtime)
+    }
+
+    // Replaces the in-memory log, e.g. with a persistent `EventLog::open` log
+    // whose chain continues from its stored tip. A log that holds evaluations
+    // is resumed from the last one: its logged pre-tick state is restored and
+    // the tick is re-executed, with the temporal violations it logged, to
+    // reproduce its record. Latch resets logged after it are re-applied, and
+    // the resumed state is logged as a `Resumed` event.
+    pub fn with_log(mut self, log: EventLog) -> Result<Self> {
+        let records = log.resume_records()?;
+        let resumed = self.resume(&records)?;
+        self.log = log;
+        if resumed {
+            self.log.append_event(
+                self.last_seq.unwrap_or_default(),
+                RuntimeEvent::Resumed {
+                    state: Box::new(self.snapshot()),
+                },
+            );
+        }
+        Ok(self)
+    }
+
+    fn resume(&mut self, records: &[EventRecord]) -> Result<bool> {
+        let Some(index) = records
+            .iter()
+            .position(|r| matches!(r.payload, EventPayload::Evaluation(_)))
+        else {
+            return Ok(false);
+        };
+        let record = &records[index];
+        let EventPayload::Evaluation(evaluation) = &record.payload else {
+            unreachable!("position matched an evaluation");
+        };
+        // The tick's own events are logged just before its evaluation.
+        let tick_start = records[..index]
+            .iter()
+            .rposition(|r| r.seq != record.seq || !is_tick_event(&r.payload))
+            .map_or(0, |i| i + 1);
+        let temporal_violations = records[tick_start..index]
+            .iter()
+            .filter_map(|r| match &r.payload {
+                EventPayload::Runtime(RuntimeEvent::TemporalViolation { formula_id }) => {
+                    Some(formula_id.clone())
+                }
+                _ => None,
+            })
+            .collect();
+
+        self.restore(&evaluation.state);
+        let log = std::mem::take(&mut self.log);
+        self.log.tip_hash = records[tick_start].prev_hash.clone();
+        self.step(
+            &evaluation.input,
+            temporal_violations,
+            log_fault(evaluation),
+        );
+        let reproduced = self.log.tip_hash == record.hash;
+        self.log = log;
+        if !reproduced {
+            bail!(
+                "runtime cannot reproduce the evaluation at seq {}",
+                record.seq
+            )
+        }
+        for record in &records[index + 1..] {
+            if let EventPayload::Runtime(RuntimeEvent::ShutdownLatchReset { .. }) = record.payload {
+                self.shutdown_latched = false;
+            }
+        }
+        Ok(true)
+    }
+
+    fn restore(&mut self, state: &RuntimeSnapshot) {
+        self.last_tick_ts_ms = state.last_tick_ts_ms;
+        self.last_seq = state.last_seq;
+        self.shutdown_latched = state.shutdown_latched;
+        self.phase = state.phase;
+        self.phase_validated = state.phase_validated;
+        self.kernel_state = state.kernel.clone();
+        self.stl = StlMonitor::default();
+    }
+
+    pub fn limits(&self) -> &Limits {
+        &self.limits
+    }
+
+    pub fn shutdown_latched(&self) -> bool {
+        self.shutdown_latched
+    }
+
+    pub fn kernel_state(&self) -> &KernelState {
+        &self.kernel_state
+    }
+
+    pub fn phase(&self) -> Option<FlightPhase> {
+        self.phase
+    }
+
+    pub fn snapshot(&self) -> RuntimeSnapshot {
+        RuntimeSnapshot {
+            last_tick_ts_ms: self.last_tick_ts_ms,
+            last_seq: self.last_seq,
+            shutdown_latched: self.shutdown_latched,
+            phase: self.phase,
+            phase_validated: self.phase_validated,
+            kernel: self.kernel_state.clone(),
+        }
     }
 
     pub fn evaluate(&mut self, input: &KernelInput) -> KernelOutput {
-        let inter_tick_ms = self
-            .last_tick_ts_ms
-            .map(|prev| input.tick.ts_ms.saturating_sub(prev));
+        let temporal_violations = self.stl.observe(input, &self.limits.guarantees.formulas);
+        let log_fault = self.log.faulted();
+        self.step(input, temporal_violations, log_fault)
+    }
 
-        let mut output = constrain(input, inter_tick_ms);
+    // Evaluates a tick given its temporal violations and the event log's
+    // fault state, which replay and resume take from the log.
+    fn step(
+        &mut self,
+        input: &KernelInput,
+        temporal_violations: Vec<String>,
+        log_fault: bool,
+    ) -> KernelOutput {
+        let state = self.snapshot();
+        let anomalies = self.tick_anomalies(input);
+        for anomaly in &anomalies {
+            self.log
+                .append_event(input.tick.seq, RuntimeEvent::TickAnomaly(*anomaly));
+        }
+        for formula_id in &temporal_violations {
+            self.log.append_event(
+                input.tick.seq,
+                RuntimeEvent::TemporalViolation {
+                    formula_id: formula_id.clone(),
+                },
+            );
+        }
+        let phase_rejected = self.advance_phase(input);
+        let clock_regressed = anomalies
+            .iter()
+            .any(|a| matches!(a, TickAnomaly::ClockRegression { .. }));
+        let ctx = TickContext {
+            inter_tick_ms: self
+                .last_tick_ts_ms
+                .filter(|_| !clock_regressed)
+                .map(|prev| input.tick.ts_ms - prev),
+            shutdown_latched: self.shutdown_latched,
+            anomalies,
+            temporal_violations: temporal_violations.len(),
+            phase: self.phase,
+            phase_validated: self.phase_validated,
+            phase_rejected,
+            log_fault,
+        };
+
+        let mut registry = CheckRegistry::new();
+        for program in &self.checks {
+            // program_checks bounds the list to MAX_PROGRAM_CHECKS.
+            let _ = registry.register(program.check.as_ref(), &program.params);
+        }
+        let mut output = constrain(input, &self.limits, &registry, &ctx, &mut self.kernel_state);
         output.contract_fingerprint = self.contract_fingerprint.clone();
-        self.log.append(input.tick.seq, &output);
-        self.last_tick_ts_ms = Some(input.tick.ts_ms);
+        self.log.append(
+            input.tick.seq,
+            Evaluation {
+                input: input.clone(),
+                state,
+                output: output.clone(),
+            },
+        );
+        if !clock_regressed {
+            self.last_tick_ts_ms = Some(input.tick.ts_ms);
+        }
+        self.last_seq = Some(
+            self.last_seq
+                .map_or(input.tick.seq, |s| s.max(input.tick.seq)),
+        );
+        if output.verdict == Verdict::Shutdown && self.limits.interlock.fault_latched_shutdown {
+            self.shutdown_latched = true;
+        }
         output
     }
 
+    // The first reported phase is adopted so that a runtime restarted in flight
+    // does not reject the current phase, but it only lowers the altitude floor
+    // once it is plausible for the observed state. Later changes must follow
+    // the declared transitions, and Ground must be plausible to be entered, or
+    // the previous phase is kept. A phase reached from a validated one by a
+    // declared transition stays validated.
+    fn advance_phase(&mut self, input: &KernelInput) -> bool {
+        let to = input.state.phase;
+        let plausible = self.phase_plausible(input);
+        let Some(from) = self.phase else {
+            self.phase = Some(to);
+            self.phase_validated = plausible;
+            return false;
+        };
+        if from == to {
+            self.phase_validated |= plausible;
+            return false;
+        }
+        if self.limits.phases.allows(from, to) && (to != FlightPhase::Ground || plausible) {
+            self.phase = Some(to);
+            self.phase_validated |= plausible;
+            self.log
+                .append_event(input.tick.seq, RuntimeEvent::PhaseTransition { from, to });
+            false
+        } else {
+            self.log.append_event(
+                input.tick.seq,
+                RuntimeEvent::PhaseTransitionRejected { from, to },
+            );
+            true
+        }
+    }
+
+    // Ground is plausible at or below the profile altitude floor and, for a
+    // fixed-wing vehicle, below its stall speed. Any other phase that lowers the
+    // floor is plausible at or below it. Non-finite readings are never
+    // plausible.
+    fn phase_plausible(&self, input: &KernelInput) -> bool {
+        let state = &input.state;
+        let on_floor = state.altitude_m() <= self.limits.invariants.min_altitude_m;
+        let caps = &self.limits.capabilities;
+        let stopped = match caps.min_airspeed_mps {
+            Some(min_airspeed_mps) if caps.fixed_wing => state.velocity_mps < min_airspeed_mps,
+            _ => true,
+        };
+        match state.phase {
+            FlightPhase::Ground => on_floor && stopped,
+            phase if self.limits.lowers_floor(phase) => on_floor,
+            _ => true,
+        }
+    }
+
+    fn tick_anomalies(
+        &self,
+        input: &KernelInput,
+    ) -> heapless::Vec<TickAnomaly, MAX_TICK_ANOMALIES> {
+        let seq = input.tick.seq;
+        let sequence = self.last_seq.and_then(|last_seq| {
+            if seq == last_seq {
+                Some(TickAnomaly::DuplicateSeq { seq })
+            } else if seq < last_seq {
+                Some(TickAnomaly::OutOfOrderSeq { seq, last_seq })
+            } else if seq > last_seq + 1 {
+                Some(TickAnomaly::SkippedSeq {
+                    expected_seq: last_seq + 1,
+                    seq,
+                })
+            } else {
+                None
+            }
+        });
+        let clock = self
+            .last_tick_ts_ms
+            .filter(|last_ts_ms| input.tick.ts_ms < *last_ts_ms)
+            .map(|last_ts_ms| TickAnomaly::ClockRegression {
+                ts_ms: input.tick.ts_ms,
+                last_ts_ms,
+            });
+        sequence.into_iter().chain(clock).collect()
+    }
+
+    // Re-executes a recorded log on a fresh runtime built from the same
+    // contract. Runtime events are regenerated by evaluation, except for
+    // latch resets and resume points, which are re-applied. Each record must
+    // reproduce both the logged pre-tick state and the logged hash. Replay
+    // may also start at a resume point, from the state it logged.
+    pub fn replay(&mut self, records: &[EventRecord]) -> Result<()> {
+        for (index, record) in records.iter().enumerate() {
+            match &record.payload {
+                EventPayload::Evaluation(evaluation) => {
+                    if self.snapshot() != evaluation.state {
+                        bail!(
+                            "runtime state diverges from the log before seq {}",
+                            record.seq
+                        )
+                    }
+                    let temporal_violations = self
+                        .stl
+                        .observe(&evaluation.input, &self.limits.guarantees.formulas);
+                    self.step(
+                        &evaluation.input,
+                        temporal_violations,
+                        log_fault(evaluation),
+                    );
+                }
+                EventPayload::Runtime(RuntimeEvent::ShutdownLatchReset { authority }) => {
+                    self.reset_shutdown_latch(record.seq, authority)?;
+                }
+                EventPayload::Runtime(RuntimeEvent::Resumed { state }) => {
+                    if index == 0 {
+                        self.log.tip_hash = record.prev_hash.clone();
+                    } else if self.snapshot() != **state {
+                        bail!(
+                            "runtime state diverges from the log at the resume point at seq {}",
+                            record.seq
+                        )
+                    }
+                    self.restore(state);
+                    self.log.append_event(
+                        record.seq,
+                        RuntimeEvent::Resumed {
+                            state: state.clone(),
+                        },
+                    );
+                }
+                EventPayload::Runtime(RuntimeEvent::RecordsDropped { count }) => {
+                    bail!("log dropped {count} records before seq {}", record.seq)
+                }
+                EventPayload::Runtime(_) => continue,
+                EventPayload::Decision(_) => bail!(
+                    "schema version {} record at seq {} has no input to replay",
+                    record.schema_version,
+                    record.seq
+                ),
+            }
+            if self.log.tip_hash != record.hash {
+                bail!("replay diverges from the log at seq {}", record.seq)
+            }
+        }
+        Ok(())
+    }
+
+    // Clears the latch after the last evaluated tick, whose seq the reset must
+    // carry, on behalf of a declared reset authority. Returns false when the
+    // latch is not set.
+    pub fn reset_shutdown_latch(&mut self, seq: u64, authority: &str) -> Result<bool> {
+        if self.last_seq != Some(seq) {
+            bail!(
+                "latch reset at seq {seq} does not follow the last evaluated tick {:?}",
+                self.last_seq
+            )
+        }
+        if !self.limits.interlock.may_reset(authority) {
+            bail!("{authority:?} is not a declared reset authority")
+        }
+        if !self.shutdown_latched {
+            return Ok(false);
+        }
+        self.shutdown_latched = false;
+        self.log.append_event(
+            seq,
+            RuntimeEvent::ShutdownLatchReset {
+                authority: authority.to_string(),
+            },
+        );
+        Ok(true)
+    }
+
     pub fn tip_hash(&self) -> String {
         self.log.tip_hash.clone()
     }
 }
+
+fn log_fault(evaluation: &Evaluation) -> bool {
+    evaluation
+        .output
+        .diagnostics
+        .iter()
+        .any(|d| d.check == CheckId::EventLog && d.tripped)
+}
+
+fn is_tick_event(payload: &EventPayload) -> bool {
+    matches!(
+        payload,
+        EventPayload::Runtime(
+            RuntimeEvent::TickAnomaly(_)
+                | RuntimeEvent::TemporalViolation { .. }
+                | RuntimeEvent::PhaseTransition { .. }
+                | RuntimeEvent::PhaseTransitionRejected { .. }
+        )
+    )
+}
@@ -0,0 +1,64 @@
+use asc_kernel_model::{
+    sanitize::input_is_finite,
+    stl::{StlFormula, StlSample},
+};
+use asc_types::model::KernelInput;
+use std::collections::VecDeque;
+
+#[derive(Debug, Clone, Default)]
+pub struct StlMonitor {
+    window: VecDeque<StlSample>,
+    // Per formula, the subformula values at each recorded tick.
+    rows: Vec<VecDeque<Vec<Option<bool>>>>,
+    started_ts_ms: Option<u64>,
+}
+
+impl StlMonitor {
+    // Records the tick and returns the ids of the formulas it violates.
+    // Formulas not yet decided are not reported. Non-finite or clock-regressed
+    // inputs are not recorded, as they are already rejected by their own
+    // checks.
+    pub fn observe(&mut self, input: &KernelInput, formulas: &[StlFormula]) -> Vec<String> {
+        let sample = StlSample::from_input(input);
+        let regressed = self
+            .window
+            .back()
+            .is_some_and(|last| sample.ts_ms < last.ts_ms);
+        if formulas.is_empty() || regressed || !input_is_finite(input) {
+            return Vec::new();
+        }
+        let started_ts_ms = *self.started_ts_ms.get_or_insert(sample.ts_ms);
+
+        let horizon_ms = formulas
+            .iter()
+            .map(|f| f.formula.horizon_ms())
+            .max()
+            .unwrap_or(0);
+        self.rows.resize_with(formulas.len(), VecDeque::new);
+        self.window.push_back(sample);
+        while self
+            .window
+            .front()
+            .is_some_and(|oldest| sample.ts_ms - oldest.ts_ms > horizon_ms)
+        {
+            self.window.pop_front();
+            for rows in &mut self.rows {
+                rows.pop_front();
+            }
+        }
+
+        let samples = self.window.make_contiguous();
+        let mut violated = Vec::new();
+        for (f, rows) in formulas.iter().zip(&mut self.rows) {
+            let mut row = Vec::with_capacity(f.formula.node_count());
+            let value = f
+                .formula
+                .eval(samples, rows.make_contiguous(), started_ts_ms, &mut row);
+            if value == Some(false) {
+                violated.push(f.id.clone());
+            }
+            rows.push_back(row);
+        }
+        violated
+    }
+}
@@ -0,0 +1,45 @@
+// @generated by tools/specgen. DO NOT EDIT.
+
+use asc_types::model::Diagnostics;
+use asc_types::model::Fingerprint;
+use asc_types::model::Tick;
+use asc_types::ReasonSet;
+use asc_types::Verdict;
+use serde::{Deserialize, Serialize};
+
+use crate::Evaluation;
+
+pub const AUDIT_SCHEMA_VERSION: u32 = 2;
+pub const AUDIT_FIELDS: [&str; 6] = [
+    "tick",
+    "verdict",
+    "reasons",
+    "diagnostics",
+    "contract_fingerprint",
+    "tip_hash",
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditRecord {
+    pub schema_version: u32,
+    pub tick: Tick,
+    pub verdict: Verdict,
+    pub reasons: ReasonSet,
+    pub diagnostics: Diagnostics,
+    pub contract_fingerprint: Fingerprint,
+    pub tip_hash: String,
+}
+
+impl AuditRecord {
+    pub fn new(evaluation: &Evaluation, tip_hash: &str) -> Self {
+        Self {
+            schema_version: AUDIT_SCHEMA_VERSION,
+            tick: evaluation.input.tick.clone(),
+            verdict: evaluation.output.verdict,
+            reasons: evaluation.output.reasons,
+            diagnostics: evaluation.output.diagnostics.clone(),
+            contract_fingerprint: evaluation.output.contract_fingerprint.clone(),
+            tip_hash: tip_hash.to_string(),
+        }
+    }
+}
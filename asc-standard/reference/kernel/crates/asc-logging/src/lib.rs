@@ -1,35 +1,289 @@
-use asc_types::model::KernelOutput;
+mod canonical;
+mod generated_audit;
+mod store;
+mod verify;
+
+use asc_kernel_model::KernelState;
+use asc_types::model::{FlightPhase, KernelInput, KernelOutput, TickAnomaly};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
+use std::io::{self, Write};
+
+pub use canonical::to_canonical_json;
+pub use generated_audit::{AuditRecord, AUDIT_FIELDS, AUDIT_SCHEMA_VERSION};
+pub use store::{read_records, FsyncPolicy, LogStore, StoreConfig};
+pub use verify::{verify_chain, verify_dir, BreakKind, ChainBreak, ChainReport};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum RuntimeEvent {
+    ShutdownLatchReset { authority: String },
+    TickAnomaly(TickAnomaly),
+    TemporalViolation { formula_id: String },
+    PhaseTransition { from: FlightPhase, to: FlightPhase },
+    PhaseTransitionRejected { from: FlightPhase, to: FlightPhase },
+    // Records dropped while the pending queue of a persistent log was full,
+    // logged once the queue has room again.
+    RecordsDropped { count: u64 },
+    // A runtime resumed the log with this state, rebuilt from its last
+    // evaluation. The STL sample history starts over from here.
+    Resumed { state: Box<RuntimeSnapshot> },
+}
+
+// Version 1 records carry only the kernel output as a `Decision`. Version 2
+// records carry an `Evaluation` and hash their schema version as well.
+// Version 3 records hash a canonical encoding instead of serde_json's output.
+pub const SCHEMA_VERSION: u32 = 3;
+
+// Runtime state as it stood before a tick was evaluated. The STL monitor's
+// sample history is not included; it is rebuilt by replaying from the start.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RuntimeSnapshot {
+    pub last_tick_ts_ms: Option<u64>,
+    pub last_seq: Option<u64>,
+    pub shutdown_latched: bool,
+    pub phase: Option<FlightPhase>,
+    // Omitted while false so records logged before it was introduced keep
+    // their hashes.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub phase_validated: bool,
+    pub kernel: KernelState,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Evaluation {
+    pub input: KernelInput,
+    pub state: RuntimeSnapshot,
+    pub output: KernelOutput,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum EventPayload {
+    Decision(Box<KernelOutput>),
+    Evaluation(Box<Evaluation>),
+    Runtime(RuntimeEvent),
+}
+
+impl EventPayload {
+    pub fn output(&self) -> Option<&KernelOutput> {
+        match self {
+            Self::Decision(output) => Some(output),
+            Self::Evaluation(evaluation) => Some(&evaluation.output),
+            Self::Runtime(_) => None,
+        }
+    }
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EventRecord {
+    #[serde(default = "legacy_schema_version")]
+    pub schema_version: u32,
     pub seq: u64,
-    pub payload: KernelOutput,
+    pub payload: EventPayload,
     pub prev_hash: String,
     pub hash: String,
 }
 
+fn legacy_schema_version() -> u32 {
+    1
+}
+
+// What a record hashes from version 3 on, as canonical JSON.
+#[derive(Serialize)]
+struct Preimage<'a> {
+    schema_version: u32,
+    seq: u64,
+    payload: &'a EventPayload,
+    prev_hash: &'a str,
+}
+
+impl EventRecord {
+    pub fn digest(
+        schema_version: u32,
+        seq: u64,
+        payload: &EventPayload,
+        prev_hash: &str,
+    ) -> String {
+        let bytes = match schema_version {
+            1 => serde_json::to_vec(&(seq, payload, prev_hash)),
+            2 => serde_json::to_vec(&(schema_version, seq, payload, prev_hash)),
+            _ => to_canonical_json(&Preimage {
+                schema_version,
+                seq,
+                payload,
+                prev_hash,
+            })
+            .map(String::into_bytes),
+        }
+        .expect("serialize event record");
+        let mut hasher = Sha256::new();
+        hasher.update(bytes);
+        hex::encode(hasher.finalize())
+    }
+
+    // The audit view of an evaluation record, in the shape declared by
+    // spec/asc/audit-log-schema.yaml. Version 1 decision records carry no
+    // tick and have no audit view.
+    pub fn audit(&self) -> Option<AuditRecord> {
+        match &self.payload {
+            EventPayload::Evaluation(evaluation) => Some(AuditRecord::new(evaluation, &self.hash)),
+            EventPayload::Decision(_) | EventPayload::Runtime(_) => None,
+        }
+    }
+}
+
+// Writes the audit records of `records` as JSONL and returns how many were
+// written.
+pub fn write_audit(records: &[EventRecord], mut out: impl Write) -> io::Result<usize> {
+    let mut written = 0;
+    for audit in records.iter().filter_map(EventRecord::audit) {
+        serde_json::to_writer(&mut out, &audit)?;
+        out.write_all(b"\n")?;
+        written += 1;
+    }
+    Ok(written)
+}
+
+// Records a persistent log holds while its store fails to accept them.
+pub const MAX_PENDING_RECORDS: usize = 1024;
+
+// An in-memory log keeps every record. A persistent log keeps only the records
+// its store has not accepted yet; they are retried on the next append. Once
+// `MAX_PENDING_RECORDS` are pending, new records are dropped unchained and
+// counted.
 #[derive(Debug, Default)]
 pub struct EventLog {
     pub records: Vec<EventRecord>,
     pub tip_hash: String,
+    store: Option<LogStore>,
+    store_error: Option<io::Error>,
+    dropped: u64,
 }
 
 impl EventLog {
-    pub fn append(&mut self, seq: u64, payload: &KernelOutput) {
+    // Reopens or creates a persistent log and resumes the chain from its last
+    // valid record.
+    pub fn open(config: StoreConfig) -> io::Result<Self> {
+        let store = LogStore::open(config)?;
+        Ok(Self {
+            records: Vec::new(),
+            tip_hash: store.tip_hash().to_string(),
+            store: Some(store),
+            store_error: None,
+            dropped: 0,
+        })
+    }
+
+    pub fn store(&self) -> Option<&LogStore> {
+        self.store.as_ref()
+    }
+
+    // The last write failure, cleared once the pending records are stored.
+    pub fn store_error(&self) -> Option<&io::Error> {
+        self.store_error.as_ref()
+    }
+
+    // The records following the second-to-last evaluation, i.e. those of the
+    // last evaluated tick and any logged after it, from which a runtime
+    // resumes. Empty while the log holds no evaluation.
+    pub fn resume_records(&self) -> io::Result<Vec<EventRecord>> {
+        let is_evaluation = |r: &EventRecord| matches!(r.payload, EventPayload::Evaluation(_));
+        let mut records = match &self.store {
+            Some(store) => store::read_tail(store.dir(), |records| {
+                records.iter().filter(|r| is_evaluation(r)).count() >= 2
+            })?,
+            None => Vec::new(),
+        };
+        records.extend(self.records.iter().cloned());
+        let Some(last) = records.iter().rposition(is_evaluation) else {
+            return Ok(Vec::new());
+        };
+        let start = records[..last]
+            .iter()
+            .rposition(is_evaluation)
+            .map_or(0, |i| i + 1);
+        records.drain(..start);
+        Ok(records)
+    }
+
+    // Records dropped since the queue last had room.
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+
+    // True while records are pending after a write failure or have been
+    // dropped, i.e. the log no longer holds every decision durably.
+    pub fn faulted(&self) -> bool {
+        self.store_error.is_some() || self.dropped > 0
+    }
+
+    // Stores any pending records and syncs the active segment.
+    pub fn sync(&mut self) -> io::Result<()> {
+        self.flush()?;
+        match &mut self.store {
+            Some(store) => store.sync(),
+            None => Ok(()),
+        }
+    }
+
+    pub fn append(&mut self, seq: u64, evaluation: Evaluation) {
+        self.append_payload(seq, EventPayload::Evaluation(Box::new(evaluation)));
+    }
+
+    pub fn append_event(&mut self, seq: u64, event: RuntimeEvent) {
+        self.append_payload(seq, EventPayload::Runtime(event));
+    }
+
+    fn append_payload(&mut self, seq: u64, payload: EventPayload) {
+        // Keep room for the record announcing the drops.
+        let needed = 1 + usize::from(self.dropped > 0);
+        if self.store.is_some() && self.records.len() + needed > MAX_PENDING_RECORDS {
+            if let Err(err) = self.flush() {
+                self.store_error = Some(err);
+            }
+            if self.records.len() + needed > MAX_PENDING_RECORDS {
+                self.dropped += 1;
+                return;
+            }
+        }
+        if self.dropped > 0 {
+            let count = std::mem::take(&mut self.dropped);
+            self.chain(
+                seq,
+                EventPayload::Runtime(RuntimeEvent::RecordsDropped { count }),
+            );
+        }
+        self.chain(seq, payload);
+        if let Err(err) = self.flush() {
+            self.store_error = Some(err);
+        }
+    }
+
+    fn chain(&mut self, seq: u64, payload: EventPayload) {
         let prev_hash = self.tip_hash.clone();
-        let bytes =
-            serde_json::to_vec(&(seq, payload, &prev_hash)).expect("serialize event record");
-        let mut hasher = Sha256::new();
-        hasher.update(bytes);
-        let hash = hex::encode(hasher.finalize());
+        let hash = EventRecord::digest(SCHEMA_VERSION, seq, &payload, &prev_hash);
         self.records.push(EventRecord {
+            schema_version: SCHEMA_VERSION,
             seq,
-            payload: payload.clone(),
+            payload,
             prev_hash,
             hash: hash.clone(),
         });
         self.tip_hash = hash;
     }
+
+    fn flush(&mut self) -> io::Result<()> {
+        let Some(store) = &mut self.store else {
+            return Ok(());
+        };
+        let mut stored = 0;
+        let result = self
+            .records
+            .iter()
+            .try_for_each(|record| store.append(record).map(|()| stored += 1));
+        self.records.drain(..stored);
+        if result.is_ok() {
+            self.store_error = None;
+        }
+        result
+    }
 }
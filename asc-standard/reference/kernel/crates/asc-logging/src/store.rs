@@ -0,0 +1,311 @@
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use crate::EventRecord;
+
+const SEGMENT_PREFIX: &str = "segment-";
+const SEGMENT_SUFFIX: &str = ".jsonl";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FsyncPolicy {
+    // Sync after every record, so an appended record survives power loss.
+    EveryRecord,
+    // Sync after every n records.
+    Every(u32),
+    // Leave syncing to the operating system.
+    Never,
+}
+
+#[derive(Debug, Clone)]
+pub struct StoreConfig {
+    pub dir: PathBuf,
+    // A segment is rotated before a record would take it past this size. A
+    // single oversized record still gets a segment of its own.
+    pub segment_max_bytes: u64,
+    pub fsync: FsyncPolicy,
+}
+
+impl StoreConfig {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            segment_max_bytes: 64 * 1024 * 1024,
+            fsync: FsyncPolicy::EveryRecord,
+        }
+    }
+}
+
+// Append-only JSONL segments, one `EventRecord` per line. Segments are synced
+// whenever they are rotated, whatever the fsync policy.
+#[derive(Debug)]
+pub struct LogStore {
+    config: StoreConfig,
+    segment: u64,
+    file: File,
+    len: u64,
+    unsynced: u32,
+    tip_hash: String,
+    truncated_bytes: u64,
+}
+
+impl LogStore {
+    // Opens the log in `config.dir`, creating it if needed. The last segment is
+    // verified against the hash chain and a torn final line left by a crash is
+    // truncated; any other invalid record is reported as corruption.
+    pub fn open(config: StoreConfig) -> io::Result<Self> {
+        if config.segment_max_bytes == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "segment_max_bytes must be > 0",
+            ));
+        }
+        fs::create_dir_all(&config.dir)?;
+        let segments = segments(&config.dir)?;
+        let Some(&segment) = segments.last() else {
+            let file = create_segment(&config.dir, 1)?;
+            return Ok(Self {
+                config,
+                segment: 1,
+                file,
+                len: 0,
+                unsynced: 0,
+                tip_hash: String::new(),
+                truncated_bytes: 0,
+            });
+        };
+
+        let prev_tip = match segments.len().checked_sub(2).map(|i| segments[i]) {
+            Some(prev) => last_hash(&segment_path(&config.dir, prev))?,
+            None => String::new(),
+        };
+        let path = segment_path(&config.dir, segment);
+        let bytes = fs::read(&path)?;
+        let (len, tip_hash) = verify_tail(&path, &bytes, prev_tip)?;
+        let file = OpenOptions::new().append(true).open(&path)?;
+        let truncated_bytes = bytes.len() as u64 - len;
+        if truncated_bytes > 0 {
+            file.set_len(len)?;
+            file.sync_all()?;
+        }
+        Ok(Self {
+            config,
+            segment,
+            file,
+            len,
+            unsynced: 0,
+            tip_hash,
+            truncated_bytes,
+        })
+    }
+
+    pub fn tip_hash(&self) -> &str {
+        &self.tip_hash
+    }
+
+    // Bytes of a torn final write removed when the log was opened.
+    pub fn truncated_bytes(&self) -> u64 {
+        self.truncated_bytes
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.config.dir
+    }
+
+    pub fn segment_path(&self) -> PathBuf {
+        segment_path(&self.config.dir, self.segment)
+    }
+
+    pub fn append(&mut self, record: &EventRecord) -> io::Result<()> {
+        let mut line = serde_json::to_vec(record)?;
+        line.push(b'\n');
+        if self.len > 0 && self.len + line.len() as u64 > self.config.segment_max_bytes {
+            self.rotate()?;
+        }
+        if let Err(err) = self.file.write_all(&line) {
+            // Drop any partial line so that a retry does not land behind it.
+            let _ = self.file.set_len(self.len);
+            return Err(err);
+        }
+        self.len += line.len() as u64;
+        self.tip_hash.clone_from(&record.hash);
+        self.unsynced += 1;
+        match self.config.fsync {
+            FsyncPolicy::EveryRecord => self.sync(),
+            FsyncPolicy::Every(n) if self.unsynced >= n => self.sync(),
+            FsyncPolicy::Every(_) | FsyncPolicy::Never => Ok(()),
+        }
+    }
+
+    pub fn sync(&mut self) -> io::Result<()> {
+        self.file.sync_data()?;
+        self.unsynced = 0;
+        Ok(())
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.sync_all()?;
+        self.file = create_segment(&self.config.dir, self.segment + 1)?;
+        self.segment += 1;
+        self.len = 0;
+        self.unsynced = 0;
+        Ok(())
+    }
+}
+
+// Reads every record of a persistent log in order, e.g. for replay. A torn
+// final line in the last segment is skipped as it is on reopen; any other
+// unreadable line is an error.
+pub fn read_records(dir: &Path) -> io::Result<Vec<EventRecord>> {
+    let segments = segments(dir)?;
+    let mut records = Vec::new();
+    for (i, segment) in segments.iter().enumerate() {
+        records.extend(read_segment(dir, *segment, i + 1 == segments.len())?);
+    }
+    Ok(records)
+}
+
+// Reads segments from the last one back until `enough` holds for the records
+// read so far, and returns those records in order.
+pub(crate) fn read_tail(
+    dir: &Path,
+    enough: impl Fn(&[EventRecord]) -> bool,
+) -> io::Result<Vec<EventRecord>> {
+    let segments = segments(dir)?;
+    let mut records = Vec::new();
+    for (i, segment) in segments.iter().enumerate().rev() {
+        let mut earlier = read_segment(dir, *segment, i + 1 == segments.len())?;
+        earlier.append(&mut records);
+        records = earlier;
+        if enough(&records) {
+            break;
+        }
+    }
+    Ok(records)
+}
+
+fn read_segment(dir: &Path, segment: u64, last: bool) -> io::Result<Vec<EventRecord>> {
+    let path = segment_path(dir, segment);
+    let bytes = fs::read(&path)?;
+    let mut records = Vec::new();
+    let mut offset = 0;
+    for line in bytes.split_inclusive(|b| *b == b'\n') {
+        let Some(line) = line.strip_suffix(b"\n") else {
+            if !last {
+                return Err(corrupt(
+                    &path,
+                    offset,
+                    "sealed segment ends in a partial line",
+                ));
+            }
+            break;
+        };
+        records.push(parse(&path, line, offset)?);
+        offset += line.len() as u64 + 1;
+    }
+    Ok(records)
+}
+
+pub(crate) fn segment_path(dir: &Path, segment: u64) -> PathBuf {
+    dir.join(format!("{SEGMENT_PREFIX}{segment:08}{SEGMENT_SUFFIX}"))
+}
+
+// Segment indices present in `dir`, in ascending order.
+pub(crate) fn segments(dir: &Path) -> io::Result<Vec<u64>> {
+    let mut segments = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let name = entry?.file_name();
+        let index = name
+            .to_str()
+            .and_then(|n| n.strip_prefix(SEGMENT_PREFIX))
+            .and_then(|n| n.strip_suffix(SEGMENT_SUFFIX))
+            .and_then(|n| n.parse::<u64>().ok());
+        segments.extend(index);
+    }
+    segments.sort_unstable();
+    Ok(segments)
+}
+
+// An empty segment left by a rotation whose directory sync failed is reused,
+// so the rotation can be retried.
+fn create_segment(dir: &Path, segment: u64) -> io::Result<File> {
+    let path = segment_path(dir, segment);
+    let file = OpenOptions::new().append(true).create(true).open(&path)?;
+    if file.metadata()?.len() > 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already holds records", path.display()),
+        ));
+    }
+    // Persist the directory entry along with the file.
+    File::open(dir)?.sync_all()?;
+    Ok(file)
+}
+
+// Hash of the last record in a sealed segment, checked against the record.
+// Rotation never seals an empty segment or one with a torn final line.
+fn last_hash(path: &Path) -> io::Result<String> {
+    let bytes = fs::read(path)?;
+    let Some(body) = bytes.strip_suffix(b"\n") else {
+        let what = if bytes.is_empty() {
+            "sealed segment is empty"
+        } else {
+            "sealed segment ends in a partial line"
+        };
+        return Err(corrupt(path, 0, what));
+    };
+    let start = body.iter().rposition(|b| *b == b'\n').map_or(0, |i| i + 1);
+    let record = parse(path, &body[start..], start as u64)?;
+    if !intact(&record) {
+        return Err(corrupt(
+            path,
+            start as u64,
+            "hash does not match the record",
+        ));
+    }
+    Ok(record.hash)
+}
+
+// Returns the length of the valid prefix and the hash of its last record.
+fn verify_tail(path: &Path, bytes: &[u8], mut tip_hash: String) -> io::Result<(u64, String)> {
+    let mut len = 0;
+    for line in bytes.split_inclusive(|b| *b == b'\n') {
+        // Only the final write can be torn, and it never reached its newline.
+        let Some(line) = line.strip_suffix(b"\n") else {
+            break;
+        };
+        let record = parse(path, line, len)?;
+        if record.prev_hash != tip_hash {
+            return Err(corrupt(path, len, "prev_hash does not link to the chain"));
+        }
+        if !intact(&record) {
+            return Err(corrupt(path, len, "hash does not match the record"));
+        }
+        tip_hash = record.hash;
+        len += line.len() as u64 + 1;
+    }
+    Ok((len, tip_hash))
+}
+
+fn intact(record: &EventRecord) -> bool {
+    record.hash
+        == EventRecord::digest(
+            record.schema_version,
+            record.seq,
+            &record.payload,
+            &record.prev_hash,
+        )
+}
+
+fn parse(path: &Path, line: &[u8], offset: u64) -> io::Result<EventRecord> {
+    serde_json::from_slice(line).map_err(|err| corrupt(path, offset, &err.to_string()))
+}
+
+fn corrupt(path: &Path, offset: u64, what: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("{} at byte {offset}: {what}", path.display()),
+    )
+}
@@ -0,0 +1,183 @@
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fs, io, path::Path};
+
+use crate::{store, EventPayload, EventRecord, RuntimeEvent};
+use asc_types::model::TickAnomaly;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BreakKind {
+    // The stored hash does not match the record's contents, or the record
+    // cannot be parsed at all.
+    ModifiedPayload,
+    // `prev_hash` names a record that is not in the stream. A record that was
+    // modified and re-hashed also surfaces here, at the record after it.
+    DeletedRecord,
+    // `prev_hash` names a record elsewhere in the stream, or a decision goes
+    // back in seq without an `OutOfOrderSeq` anomaly announcing it.
+    ReorderedRecord,
+    // The chain is intact but does not end at the expected tip hash.
+    ForgedTip,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainBreak {
+    // Index of the first offending record; `records` for a forged tip that no
+    // record in the stream carries.
+    pub index: usize,
+    pub seq: Option<u64>,
+    pub kind: BreakKind,
+    pub detail: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainReport {
+    pub records: usize,
+    // Hash of the last record before the first break.
+    pub tip_hash: String,
+    pub first_break: Option<ChainBreak>,
+}
+
+impl ChainReport {
+    pub fn is_intact(&self) -> bool {
+        self.first_break.is_none()
+    }
+}
+
+// Walks the chain from the empty genesis hash. When `expected_tip` is given,
+// e.g. from an evidence manifest, the stream must end exactly there.
+pub fn verify_chain(records: &[EventRecord], expected_tip: Option<&str>) -> ChainReport {
+    let positions: HashMap<&str, usize> = records
+        .iter()
+        .enumerate()
+        .map(|(i, r)| (r.hash.as_str(), i))
+        .collect();
+    let mut report = ChainReport {
+        records: records.len(),
+        tip_hash: String::new(),
+        first_break: None,
+    };
+    let mut last_decision_seq = None;
+    let mut announced_seq = None;
+
+    for (index, record) in records.iter().enumerate() {
+        let broken = |kind, detail: String| ChainBreak {
+            index,
+            seq: Some(record.seq),
+            kind,
+            detail,
+        };
+        let computed = EventRecord::digest(
+            record.schema_version,
+            record.seq,
+            &record.payload,
+            &record.prev_hash,
+        );
+        if computed != record.hash {
+            report.first_break = Some(broken(
+                BreakKind::ModifiedPayload,
+                format!(
+                    "stored hash {} but contents hash to {computed}",
+                    record.hash
+                ),
+            ));
+            return report;
+        }
+        if record.prev_hash != report.tip_hash {
+            let kind = match positions.get(record.prev_hash.as_str()) {
+                Some(_) => BreakKind::ReorderedRecord,
+                None => BreakKind::DeletedRecord,
+            };
+            report.first_break = Some(broken(
+                kind,
+                format!(
+                    "prev_hash {:?} does not link to {:?}",
+                    record.prev_hash, report.tip_hash
+                ),
+            ));
+            return report;
+        }
+        match &record.payload {
+            EventPayload::Runtime(RuntimeEvent::TickAnomaly(TickAnomaly::OutOfOrderSeq {
+                seq,
+                ..
+            })) => announced_seq = Some(*seq),
+            EventPayload::Decision(_) | EventPayload::Evaluation(_) => {
+                if last_decision_seq.is_some_and(|last| record.seq < last)
+                    && announced_seq != Some(record.seq)
+                {
+                    report.first_break = Some(broken(
+                        BreakKind::ReorderedRecord,
+                        format!(
+                            "decision seq {} follows seq {} without an OutOfOrderSeq anomaly",
+                            record.seq,
+                            last_decision_seq.unwrap_or_default()
+                        ),
+                    ));
+                    return report;
+                }
+                last_decision_seq = Some(record.seq);
+                announced_seq = None;
+            }
+            EventPayload::Runtime(_) => {}
+        }
+        report.tip_hash.clone_from(&record.hash);
+    }
+
+    if let Some(expected) = expected_tip.filter(|tip| *tip != report.tip_hash) {
+        let index = positions.get(expected).map_or(records.len(), |i| i + 1);
+        report.first_break = Some(ChainBreak {
+            index,
+            seq: records.get(index).map(|r| r.seq),
+            kind: BreakKind::ForgedTip,
+            detail: format!("chain ends at {:?}, expected {expected:?}", report.tip_hash),
+        });
+    }
+    report
+}
+
+// Verifies every segment of a persistent log in order. A torn final line in
+// the last segment was never acknowledged and is not part of the chain.
+pub fn verify_dir(dir: &Path, expected_tip: Option<&str>) -> io::Result<ChainReport> {
+    let mut records = Vec::new();
+    let mut unreadable = None;
+    let segments = store::segments(dir)?;
+    'segments: for (i, segment) in segments.iter().enumerate() {
+        let bytes = fs::read(store::segment_path(dir, *segment))?;
+        for line in bytes.split_inclusive(|b| *b == b'\n') {
+            let Some(line) = line.strip_suffix(b"\n") else {
+                if i + 1 < segments.len() {
+                    unreadable = Some("sealed segment ends in a partial line".to_string());
+                    break 'segments;
+                }
+                break;
+            };
+            match serde_json::from_slice(line) {
+                Ok(record) => records.push(record),
+                Err(err) => {
+                    unreadable = Some(err.to_string());
+                    break 'segments;
+                }
+            }
+        }
+    }
+
+    let mut report = verify_chain(&records, expected_tip);
+    if let Some(err) = unreadable {
+        // Records after an unreadable line cannot be attributed, so the
+        // unreadable line is the first break unless an earlier one was found.
+        if report
+            .first_break
+            .as_ref()
+            .is_none_or(|b| b.kind == BreakKind::ForgedTip)
+        {
+            report.records = records.len() + 1;
+            report.first_break = Some(ChainBreak {
+                index: records.len(),
+                seq: None,
+                kind: BreakKind::ModifiedPayload,
+                detail: format!("record cannot be parsed: {err}"),
+            });
+        }
+    }
+    Ok(report)
+}
@@ -0,0 +1,31 @@
+// Serde adapter for observed frames. A frame longer than FRAME_CAPACITY cannot
+// name the contract frame, so rather than failing the whole input it is read
+// as the empty frame, which the kernel reports as StateInvalidFrame. The
+// contract frame itself is never empty.
+
+use core::fmt;
+use serde::{de, Deserializer, Serializer};
+
+use crate::model::Frame;
+
+pub fn serialize<S: Serializer>(frame: &Frame, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(frame)
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Frame, D::Error> {
+    deserializer.deserialize_str(FrameVisitor)
+}
+
+struct FrameVisitor;
+
+impl de::Visitor<'_> for FrameVisitor {
+    type Value = Frame;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a frame name")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Frame, E> {
+        Ok(Frame::try_from(v).unwrap_or_default())
+    }
+}
@@ -19,4 +19,45 @@ pub enum ReasonCode {
     InvariantViolation,
     InputStale,
     DeadlineMiss,
+    GeofenceViolation,
+    PowerLimitExceeded,
+    InterlockDisarmed,
+    ShutdownLatched,
+    NonFiniteInput,
+    TickSequenceViolation,
+    ClockRegression,
+    DeescalationPending,
+    BarrierProjection,
+    SlewLimited,
+    AirspeedBelowStall,
+    PayloadExceeded,
+    PhaseTransitionInvalid,
+    EventLogFault,
+}
+
+impl ReasonCode {
+    pub const ALL: [Self; 22] = [
+        Self::StateInvalidFrame,
+        Self::StateOutOfBounds,
+        Self::FlowConstraintViolation,
+        Self::EnergyBudgetExceeded,
+        Self::TemporalGuaranteeViolation,
+        Self::InvariantViolation,
+        Self::InputStale,
+        Self::DeadlineMiss,
+        Self::GeofenceViolation,
+        Self::PowerLimitExceeded,
+        Self::InterlockDisarmed,
+        Self::ShutdownLatched,
+        Self::NonFiniteInput,
+        Self::TickSequenceViolation,
+        Self::ClockRegression,
+        Self::DeescalationPending,
+        Self::BarrierProjection,
+        Self::SlewLimited,
+        Self::AirspeedBelowStall,
+        Self::PayloadExceeded,
+        Self::PhaseTransitionInvalid,
+        Self::EventLogFault,
+    ];
 }
@@ -1,6 +1,12 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub mod float;
+pub mod frame;
 mod generated_reason_codes;
 pub mod model;
+pub mod reason_set;
 pub mod verdict;
 
 pub use generated_reason_codes::{ReasonCode, Severity};
+pub use reason_set::ReasonSet;
 pub use verdict::Verdict;
@@ -1,40 +1,155 @@
-use crate::{ReasonCode, Severity, Verdict};
+use crate::{ReasonCode, ReasonSet, Severity, Verdict};
 use serde::{Deserialize, Serialize};
 
+// Fixed capacities keep inputs and outputs allocation-free; contract
+// fingerprints are hex SHA-256 digests.
+pub const FRAME_CAPACITY: usize = 16;
+pub const FINGERPRINT_CAPACITY: usize = 64;
+pub const MAX_DIAGNOSTICS: usize = 48;
+pub const MAX_EXTENSION_SIGNALS: usize = 8;
+pub const EXTENSION_NAME_CAPACITY: usize = 32;
+
+pub type Frame = heapless::String<FRAME_CAPACITY>;
+pub type Fingerprint = heapless::String<FINGERPRINT_CAPACITY>;
+pub type Diagnostics = heapless::Vec<CheckOutcome, MAX_DIAGNOSTICS>;
+pub type Extension = heapless::Vec<ExtensionSignal, MAX_EXTENSION_SIGNALS>;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Tick {
     pub seq: u64,
     pub ts_ms: u64,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TickAnomaly {
+    DuplicateSeq { seq: u64 },
+    OutOfOrderSeq { seq: u64, last_seq: u64 },
+    SkippedSeq { expected_seq: u64, seq: u64 },
+    ClockRegression { ts_ms: u64, last_ts_ms: u64 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum FlightPhase {
+    Ground,
+    Takeoff,
+    Cruise,
+    Approach,
+    Landing,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ObservedState {
-    pub frame: String,
+    #[serde(with = "crate::frame")]
+    pub frame: Frame,
+    #[serde(with = "crate::float::array")]
     pub position_m: [f64; 3],
+    #[serde(with = "crate::float")]
     pub velocity_mps: f64,
+    // Absent from inputs logged before it was introduced; omitted again when
+    // unset so those records keep their hashes.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "crate::float::option"
+    )]
+    pub airspeed_mps: Option<f64>,
+    #[serde(with = "crate::float")]
     pub bank_deg: f64,
+    #[serde(default, with = "crate::float")]
+    pub pitch_deg: f64,
+    #[serde(with = "crate::float")]
     pub soc_percent: f64,
+    #[serde(with = "crate::float")]
+    pub power_w: f64,
+    #[serde(with = "crate::float")]
+    pub battery_capacity_wh: f64,
     pub input_age_ms: u64,
+    // Absent from inputs that predate the interlock; read as disarmed.
+    #[serde(default)]
+    pub armed: bool,
+    #[serde(with = "crate::float")]
+    pub payload_kg: f64,
+    pub phase: FlightPhase,
+}
+
+impl ObservedState {
+    // `position_m` is [north, east, down]; altitude is measured up from the
+    // origin, as are `min_altitude_m` and geofence floors and ceilings.
+    pub fn altitude_m(&self) -> f64 {
+        -self.position_m[2]
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Intent {
+    #[serde(with = "crate::float::array")]
     pub desired_rates_dps: [f64; 3],
+    #[serde(with = "crate::float")]
     pub desired_climb_mps: f64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ConstrainedCommand {
     pub applied_rates_dps: [f64; 3],
     pub applied_climb_mps: f64,
     pub shutdown: bool,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CheckId {
+    InputFinite,
+    ShutdownLatch,
+    Interlock,
+    Frame,
+    Speed,
+    PositionBounds,
+    Attitude,
+    FlowRates,
+    Barrier,
+    SocFloor,
+    Endurance,
+    Power,
+    InputAge,
+    TickSequence,
+    Clock,
+    TemporalFormula,
+    TickInterval,
+    Deadline,
+    Geofence,
+    MinAltitude,
+    MaxBank,
+    Deescalation,
+    Slew,
+    Airspeed,
+    HoverDescent,
+    Payload,
+    Phase,
+    EventLog,
+    // A program check, by its position in spec/asc/checks.yaml.
+    Program(u16),
+}
+
+// Every check reports an outcome each tick. Untripped outcomes carry an Allow
+// verdict and Info severity; `margin` is positive while within the limit.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CheckOutcome {
+    pub check: CheckId,
     pub verdict: Verdict,
     pub reason: ReasonCode,
     pub severity: Severity,
+    pub tripped: bool,
+    pub measured: Option<f64>,
+    pub limit: Option<f64>,
+    pub margin: Option<f64>,
+}
+
+// A program-specific signal, such as a link quality or motor temperature, read
+// only by program checks.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExtensionSignal {
+    pub name: heapless::String<EXTENSION_NAME_CAPACITY>,
+    #[serde(with = "crate::float")]
+    pub value: f64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -42,12 +157,30 @@ pub struct KernelInput {
     pub tick: Tick,
     pub state: ObservedState,
     pub intent: Intent,
+    // Omitted while empty so inputs logged before it was introduced keep their
+    // hashes.
+    #[serde(default, skip_serializing_if = "no_signals")]
+    pub extension: Extension,
+}
+
+impl KernelInput {
+    pub fn extension_signal(&self, name: &str) -> Option<f64> {
+        self.extension
+            .iter()
+            .find(|signal| signal.name == name)
+            .map(|signal| signal.value)
+    }
+}
+
+fn no_signals(extension: &Extension) -> bool {
+    extension.is_empty()
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct KernelOutput {
     pub verdict: Verdict,
-    pub reasons: Vec<ReasonCode>,
+    pub reasons: ReasonSet,
+    pub diagnostics: Diagnostics,
     pub command: ConstrainedCommand,
-    pub contract_fingerprint: String,
+    pub contract_fingerprint: Fingerprint,
 }
@@ -0,0 +1,129 @@
+use core::fmt;
+use serde::{
+    de::{SeqAccess, Visitor},
+    ser::SerializeSeq,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::ReasonCode;
+
+const _: () = assert!(ReasonCode::ALL.len() <= u64::BITS as usize);
+
+// Fixed-capacity set of reason codes, one bit per code in tuple.yaml
+// declaration order. Iteration and the serialized list follow that order.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ReasonSet(u64);
+
+impl ReasonSet {
+    pub const fn new() -> Self {
+        Self(0)
+    }
+
+    pub fn insert(&mut self, reason: ReasonCode) {
+        self.0 |= 1 << reason as u32;
+    }
+
+    pub fn contains(&self, reason: &ReasonCode) -> bool {
+        self.0 & (1 << *reason as u32) != 0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn iter(&self) -> Iter {
+        Iter(self.0)
+    }
+}
+
+impl fmt::Debug for ReasonSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+pub struct Iter(u64);
+
+impl Iterator for Iter {
+    type Item = ReasonCode;
+
+    fn next(&mut self) -> Option<ReasonCode> {
+        if self.0 == 0 {
+            return None;
+        }
+        let index = self.0.trailing_zeros() as usize;
+        self.0 &= self.0 - 1;
+        Some(ReasonCode::ALL[index])
+    }
+}
+
+impl IntoIterator for ReasonSet {
+    type Item = ReasonCode;
+    type IntoIter = Iter;
+
+    fn into_iter(self) -> Iter {
+        self.iter()
+    }
+}
+
+impl FromIterator<ReasonCode> for ReasonSet {
+    fn from_iter<I: IntoIterator<Item = ReasonCode>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for reason in iter {
+            set.insert(reason);
+        }
+        set
+    }
+}
+
+impl<const N: usize> From<[ReasonCode; N]> for ReasonSet {
+    fn from(reasons: [ReasonCode; N]) -> Self {
+        reasons.into_iter().collect()
+    }
+}
+
+// Compares as the ordered list the set serializes to.
+#[cfg(feature = "std")]
+impl PartialEq<Vec<ReasonCode>> for ReasonSet {
+    fn eq(&self, other: &Vec<ReasonCode>) -> bool {
+        self.iter().eq(other.iter().copied())
+    }
+}
+
+impl Serialize for ReasonSet {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.len()))?;
+        for reason in self.iter() {
+            seq.serialize_element(&reason)?;
+        }
+        seq.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for ReasonSet {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ReasonSetVisitor;
+
+        impl<'de> Visitor<'de> for ReasonSetVisitor {
+            type Value = ReasonSet;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a list of reason codes")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<ReasonSet, A::Error> {
+                let mut set = ReasonSet::new();
+                while let Some(reason) = seq.next_element()? {
+                    set.insert(reason);
+                }
+                Ok(set)
+            }
+        }
+
+        deserializer.deserialize_seq(ReasonSetVisitor)
+    }
+}
@@ -4,6 +4,13 @@ mod normalize;
 mod render;
 
 use anyhow::{bail, Context, Result};
+use asc_kernel_model::{
+    checks::{CHECK_PARAM_NAME_CAPACITY, MAX_CHECK_PARAMS, MAX_PROGRAM_CHECKS},
+    geofence::{FENCE_ID_CAPACITY, MAX_FENCE_EXCLUSIONS, MAX_FENCE_VERTICES},
+    limits::{AUTHORITY_CAPACITY, MAX_RESET_AUTHORITIES},
+    stl::{self, Formula, StlFormula},
+};
+use asc_types::model::FRAME_CAPACITY;
 use clap::Parser;
 use model::*;
 use sha2::{Digest, Sha256};
@@ -32,29 +39,87 @@ fn run(args: Args) -> Result<()> {
     let energy_raw = io::read(&spec_root.join("asc/energy-contract.yaml"))?;
     let guarantees_raw = io::read(&spec_root.join("asc/guarantees-stl.yaml"))?;
     let inv_raw = io::read(&spec_root.join("asc/invariants-rcbf.yaml"))?;
+    let interlock_raw = io::read(&spec_root.join("asc/interlock-gate.yaml"))?;
+    let deescalation_raw = io::read(&spec_root.join("asc/deescalation.yaml"))?;
+    let fallbacks_raw = io::read(&spec_root.join("asc/fallbacks.yaml"))?;
+    let phases_raw = io::read(&spec_root.join("asc/phases.yaml"))?;
+    let checks_raw = io::read(&spec_root.join("asc/checks.yaml"))?;
+    let audit_raw = io::read(&spec_root.join("asc/audit-log-schema.yaml"))?;
     let profile_raw = io::read(&spec_root.join(format!("profiles/{}.yaml", args.profile)))
         .with_context(|| "profile file missing")?;
 
     let tuple: TupleSpec = serde_yaml::from_str(&tuple_raw)?;
-    let state: StateSpec = serde_yaml::from_str(&state_raw)?;
-    let flow: FlowSpec = serde_yaml::from_str(&flow_raw)?;
-    let energy: EnergySpec = serde_yaml::from_str(&energy_raw)?;
-    let guarantees: GuaranteesSpec = serde_yaml::from_str(&guarantees_raw)?;
-    let inv: InvariantsSpec = serde_yaml::from_str(&inv_raw)?;
-    let profile: ProfileSpec = serde_yaml::from_str(&profile_raw)?;
+    let mut state: StateSpec = serde_yaml::from_str(&state_raw)?;
+    let mut flow: FlowSpec = serde_yaml::from_str(&flow_raw)?;
+    let mut energy: EnergySpec = serde_yaml::from_str(&energy_raw)?;
+    let mut guarantees: Guarantees = serde_yaml::from_str(&guarantees_raw)?;
+    let mut inv: Invariants = serde_yaml::from_str(&inv_raw)?;
+    let interlock: InterlockSpec = serde_yaml::from_str(&interlock_raw)?;
+    let deescalation: DeescalationSpec = serde_yaml::from_str(&deescalation_raw)?;
+    let mut fallbacks: Fallbacks = serde_yaml::from_str(&fallbacks_raw)?;
+    let phases: PhasesSpec = serde_yaml::from_str(&phases_raw)?;
+    let checks: ChecksSpec = serde_yaml::from_str(&checks_raw)?;
+    let audit: AuditSchemaSpec = serde_yaml::from_str(&audit_raw)?;
+    let profile: Profile = serde_yaml::from_str(&profile_raw)?;
+    let geofence_raw = profile
+        .geofence
+        .as_ref()
+        .map(|name| io::read(&spec_root.join(format!("geofences/{name}.yaml"))))
+        .transpose()?;
+    let geofence: Option<GeofenceSpec> = geofence_raw
+        .as_deref()
+        .map(serde_yaml::from_str)
+        .transpose()?;
+
+    asc_contract::apply_overrides(
+        &profile.name,
+        &profile.overrides,
+        asc_contract::Sections {
+            state: &mut state,
+            flow: &mut flow,
+            energy: &mut energy,
+            guarantees: &mut guarantees,
+            invariants: &mut inv,
+            fallbacks: &mut fallbacks,
+        },
+    )?;
+    let merged_raw =
+        serde_yaml::to_string(&(&state, &flow, &energy, &guarantees, &inv, &fallbacks))?;
 
     validate(&tuple, &state, &flow, &energy, &guarantees, &inv, &profile)?;
+    validate_geofence(geofence.as_ref(), &state, &inv, &profile)?;
+    asc_contract::validate_fallbacks(&fallbacks, &flow)?;
+    asc_contract::validate_capabilities(&profile.capabilities, &fallbacks)?;
+    asc_contract::validate_transition(&profile)?;
+    asc_contract::validate_attitude(&state, &inv)?;
+    validate_formulas(&guarantees, &profile)?;
+    asc_contract::validate_phases(&phases)?;
+    validate_checks(&checks, &tuple)?;
+    validate_interlock(&interlock)?;
+    validate_audit(&audit)?;
+    asc_contract::validate_deescalation(&deescalation, &state, &energy)?;
 
-    let canonical = [
+    let mut canonical = [
         normalize::canonicalize(&tuple_raw),
         normalize::canonicalize(&state_raw),
         normalize::canonicalize(&flow_raw),
         normalize::canonicalize(&energy_raw),
         normalize::canonicalize(&guarantees_raw),
         normalize::canonicalize(&inv_raw),
+        normalize::canonicalize(&interlock_raw),
+        normalize::canonicalize(&deescalation_raw),
+        normalize::canonicalize(&fallbacks_raw),
+        normalize::canonicalize(&phases_raw),
+        normalize::canonicalize(&checks_raw),
+        normalize::canonicalize(&audit_raw),
         normalize::canonicalize(&profile_raw),
+        normalize::canonicalize(&merged_raw),
     ]
     .join("\n");
+    if let Some(raw) = &geofence_raw {
+        canonical.push('\n');
+        canonical.push_str(&normalize::canonicalize(raw));
+    }
 
     let mut hasher = Sha256::new();
     hasher.update(canonical.as_bytes());
@@ -62,8 +127,20 @@ fn run(args: Args) -> Result<()> {
     println!("spec-hash:{}", digest);
 
     let reason_out = render::render_reason_codes(&tuple);
-    let thresholds_out = render::render_thresholds(&state, &flow, &energy, &guarantees, &inv);
-    let profile_out = render::render_profile(&profile);
+    let mut thresholds_out = render::render_thresholds(
+        &state,
+        &flow,
+        &energy,
+        &guarantees,
+        &inv,
+        &interlock,
+        &deescalation,
+    );
+    thresholds_out.push_str(&render::render_fallbacks(&fallbacks));
+    thresholds_out.push_str(&render::render_formulas(&guarantees));
+    thresholds_out.push_str(&render::render_phases(&phases));
+    let profile_out = render::render_profile(&profile, geofence.as_ref());
+    let audit_out = render::render_audit(&audit);
 
     io::write_if_changed(
         &args
@@ -83,6 +160,12 @@ fn run(args: Args) -> Result<()> {
             .join("reference/kernel/crates/asc-kernel-model/src/generated_profile.rs"),
         &profile_out,
     )?;
+    io::write_if_changed(
+        &args
+            .repo_root
+            .join("reference/kernel/crates/asc-logging/src/generated_audit.rs"),
+        &audit_out,
+    )?;
     io::write_if_changed(
         &args.repo_root.join("evidence/manifests/spec-hash.txt"),
         &format!("{}\n", digest),
@@ -96,11 +179,11 @@ fn validate(
     state: &StateSpec,
     flow: &FlowSpec,
     energy: &EnergySpec,
-    guarantees: &GuaranteesSpec,
-    inv: &InvariantsSpec,
-    profile: &ProfileSpec,
+    guarantees: &Guarantees,
+    inv: &Invariants,
+    profile: &Profile,
 ) -> Result<()> {
-    const REQUIRED_REASONS: [&str; 8] = [
+    const REQUIRED_REASONS: [&str; 22] = [
         "StateInvalidFrame",
         "StateOutOfBounds",
         "FlowConstraintViolation",
@@ -109,12 +192,30 @@ fn validate(
         "InvariantViolation",
         "InputStale",
         "DeadlineMiss",
+        "GeofenceViolation",
+        "PowerLimitExceeded",
+        "InterlockDisarmed",
+        "ShutdownLatched",
+        "NonFiniteInput",
+        "TickSequenceViolation",
+        "ClockRegression",
+        "DeescalationPending",
+        "BarrierProjection",
+        "SlewLimited",
+        "AirspeedBelowStall",
+        "PayloadExceeded",
+        "PhaseTransitionInvalid",
+        "EventLogFault",
     ];
 
     let unique = tuple.reason_codes.iter().collect::<BTreeSet<_>>();
     if unique.len() != tuple.reason_codes.len() {
         bail!("tuple.reason_codes contains duplicates")
     }
+    // The kernel carries reasons as a u64 bitset (asc_types::ReasonSet).
+    if tuple.reason_codes.len() > 64 {
+        bail!("tuple.reason_codes must declare at most 64 codes")
+    }
     for required in REQUIRED_REASONS {
         if !tuple.reason_codes.iter().any(|r| r == required) {
             bail!("tuple.reason_codes missing required value: {required}")
@@ -123,6 +224,17 @@ fn validate(
     if !tuple.severities.iter().any(|s| s == "Critical") {
         bail!("tuple.severities must include Critical")
     }
+    // Frames are carried as fixed-capacity strings so the kernel needs no
+    // allocator.
+    if state.frame.is_empty() || state.frame.len() > FRAME_CAPACITY {
+        bail!("state.frame must be 1 to {FRAME_CAPACITY} bytes")
+    }
+    if (0..3).any(|axis| state.position_bounds_m.min[axis] >= state.position_bounds_m.max[axis]) {
+        bail!("state.position_bounds_m.min must be < max on every axis")
+    }
+    if state.attitude_limit_deg <= 0.0 || state.attitude_limit_deg > 180.0 {
+        bail!("state.attitude_limit_deg must be in (0, 180]")
+    }
     if state.max_speed_mps <= 0.0 {
         bail!("state.max_speed_mps must be > 0")
     }
@@ -130,12 +242,17 @@ fn validate(
         || flow.max_pitch_rate_dps <= 0.0
         || flow.max_yaw_rate_dps <= 0.0
         || flow.max_climb_rate_mps <= 0.0
+        || flow.max_rate_step_dps <= 0.0
+        || flow.max_climb_step_mps <= 0.0
     {
         bail!("flow limits must be > 0")
     }
     if !(0.0..=100.0).contains(&energy.min_soc_percent) {
         bail!("energy.min_soc_percent must be in [0, 100]")
     }
+    if energy.reserve_endurance_s < 0.0 || energy.max_power_w <= 0.0 {
+        bail!("energy reserve must be >= 0 and max_power_w > 0")
+    }
     if guarantees.deadline_ms == 0 || guarantees.max_tick_interval_ms == 0 {
         bail!("guarantee timings must be > 0")
     }
@@ -145,9 +262,149 @@ fn validate(
     if inv.min_altitude_m < 0.0 || inv.max_bank_deg <= 0.0 {
         bail!("invariant bounds invalid")
     }
+    if inv.barrier_gain_per_s <= 0.0 {
+        bail!("invariants.barrier_gain_per_s must be > 0")
+    }
     if profile.timing.control_hz == 0 || profile.timing.deadline_ms == 0 {
         bail!("profile timing values must be > 0")
     }
 
     Ok(())
 }
+
+fn validate_geofence(
+    fence: Option<&GeofenceSpec>,
+    state: &StateSpec,
+    inv: &Invariants,
+    profile: &Profile,
+) -> Result<()> {
+    let Some(fence) = fence else {
+        if inv.require_geofence {
+            bail!(
+                "profile {} requires a geofence but none is configured",
+                profile.name
+            )
+        }
+        return Ok(());
+    };
+    asc_contract::validate_geofence(fence, state)?;
+    // The baked limits hold the fence in the kernel's fixed-capacity storage.
+    if fence.exclusions.len() > MAX_FENCE_EXCLUSIONS {
+        bail!("geofence declares more than {MAX_FENCE_EXCLUSIONS} exclusions")
+    }
+    for zone in std::iter::once(&fence.inclusion).chain(&fence.exclusions) {
+        if zone.id.len() > FENCE_ID_CAPACITY {
+            bail!(
+                "geofence zone id {} exceeds {FENCE_ID_CAPACITY} bytes",
+                zone.id
+            )
+        }
+        if let FenceShapeSpec::Polygon { vertices_m } = &zone.shape {
+            if vertices_m.len() > MAX_FENCE_VERTICES {
+                bail!(
+                    "geofence zone {} polygon has more than {MAX_FENCE_VERTICES} vertices",
+                    zone.id
+                )
+            }
+        }
+    }
+    Ok(())
+}
+
+fn validate_interlock(interlock: &InterlockSpec) -> Result<()> {
+    asc_contract::validate_interlock(interlock)?;
+    // The baked limits hold the authorities in fixed-capacity storage.
+    if interlock.reset_authorities.len() > MAX_RESET_AUTHORITIES {
+        bail!("interlock declares more than {MAX_RESET_AUTHORITIES} reset authorities")
+    }
+    if let Some(authority) = interlock
+        .reset_authorities
+        .iter()
+        .find(|a| a.len() > AUTHORITY_CAPACITY)
+    {
+        bail!("reset authority {authority} exceeds {AUTHORITY_CAPACITY} bytes")
+    }
+    Ok(())
+}
+
+fn validate_checks(checks: &ChecksSpec, tuple: &TupleSpec) -> Result<()> {
+    asc_contract::validate_checks(checks)?;
+    // The kernel registers program checks and their parameters in
+    // fixed-capacity storage.
+    if checks.checks.len() > MAX_PROGRAM_CHECKS {
+        bail!("checks declares more than {MAX_PROGRAM_CHECKS} program checks")
+    }
+    for check in &checks.checks {
+        if check.params.len() > MAX_CHECK_PARAMS {
+            bail!(
+                "program check {} declares more than {MAX_CHECK_PARAMS} parameters",
+                check.id
+            )
+        }
+        if let Some(name) = check
+            .params
+            .keys()
+            .find(|name| name.len() > CHECK_PARAM_NAME_CAPACITY)
+        {
+            bail!(
+                "program check {} parameter {name} exceeds {CHECK_PARAM_NAME_CAPACITY} bytes",
+                check.id
+            )
+        }
+        let reason = format!("{:?}", check.reason);
+        if !tuple.reason_codes.contains(&reason) {
+            bail!(
+                "program check {} uses unknown reason code {reason}",
+                check.id
+            )
+        }
+        let severity = format!("{:?}", check.severity);
+        if !tuple.severities.contains(&severity) {
+            bail!(
+                "program check {} uses unknown severity {severity}",
+                check.id
+            )
+        }
+    }
+    Ok(())
+}
+
+fn validate_audit(audit: &AuditSchemaSpec) -> Result<()> {
+    if audit.schema_version == 0 {
+        bail!("audit schema_version must be >= 1")
+    }
+    let mut fields = BTreeSet::new();
+    for field in &audit.fields {
+        if !fields.insert(field.as_str()) {
+            bail!("audit field {field} is duplicated")
+        }
+        if !AUDIT_FIELDS.iter().any(|(name, ..)| name == field) {
+            bail!(
+                "unknown audit field {field}, expected one of {:?}",
+                AUDIT_FIELDS.map(|(name, ..)| name)
+            )
+        }
+    }
+    Ok(())
+}
+
+fn validate_formulas(guarantees: &Guarantees, profile: &Profile) -> Result<()> {
+    let mut ids = BTreeSet::new();
+    let mut formulas = Vec::new();
+    for formula in &guarantees.formulas {
+        if formula.id.is_empty() || !ids.insert(formula.id.as_str()) {
+            bail!(
+                "guarantee formula id {:?} is empty or duplicated",
+                formula.id
+            )
+        }
+        formulas.push(StlFormula {
+            id: formula.id.clone(),
+            formula: Formula::parse(&formula.formula)
+                .with_context(|| format!("guarantee formula {} is invalid", formula.id))?,
+        });
+    }
+    stl::validate(&formulas, profile.timing.control_hz, guarantees.deadline_ms)
+        .context("guarantee formulas exceed the monitor budget")?;
+    Ok(())
+}
@@ -1,7 +1,48 @@
 #![allow(dead_code)]
 
+pub use asc_contract::{
+    Deescalation as DeescalationSpec, EnergySpec, Fallbacks, FenceShapeSpec, FenceZoneSpec,
+    FlowSpec, GeofenceSpec, Guarantees, InterlockGate as InterlockSpec, Invariants, ModeLimitsSpec,
+    Phases as PhasesSpec, Profile, ProgramChecks as ChecksSpec, StateSpec,
+};
 use serde::Deserialize;
 
+// Fields an audit record may declare: name, Rust type, the path it is
+// imported from and how the value is taken from the evaluation it summarizes.
+pub const AUDIT_FIELDS: [(&str, &str, Option<&str>, &str); 6] = [
+    (
+        "tick",
+        "Tick",
+        Some("asc_types::model::Tick"),
+        "evaluation.input.tick.clone()",
+    ),
+    (
+        "verdict",
+        "Verdict",
+        Some("asc_types::Verdict"),
+        "evaluation.output.verdict",
+    ),
+    (
+        "reasons",
+        "ReasonSet",
+        Some("asc_types::ReasonSet"),
+        "evaluation.output.reasons",
+    ),
+    (
+        "diagnostics",
+        "Diagnostics",
+        Some("asc_types::model::Diagnostics"),
+        "evaluation.output.diagnostics.clone()",
+    ),
+    (
+        "contract_fingerprint",
+        "Fingerprint",
+        Some("asc_types::model::Fingerprint"),
+        "evaluation.output.contract_fingerprint.clone()",
+    ),
+    ("tip_hash", "String", None, "tip_hash.to_string()"),
+];
+
 #[derive(Debug, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct TupleSpec {
@@ -12,72 +53,7 @@ pub struct TupleSpec {
 
 #[derive(Debug, Deserialize)]
 #[serde(deny_unknown_fields)]
-pub struct StateSpec {
-    pub frame: String,
-    pub position_bounds_m: PositionBounds,
-    pub attitude_limit_deg: f64,
-    pub max_speed_mps: f64,
-}
-
-#[derive(Debug, Deserialize)]
-#[serde(deny_unknown_fields)]
-pub struct PositionBounds {
-    pub min: [f64; 3],
-    pub max: [f64; 3],
-}
-
-#[derive(Debug, Deserialize)]
-#[serde(deny_unknown_fields)]
-pub struct FlowSpec {
-    pub max_roll_rate_dps: f64,
-    pub max_pitch_rate_dps: f64,
-    pub max_yaw_rate_dps: f64,
-    pub max_climb_rate_mps: f64,
-}
-
-#[derive(Debug, Deserialize)]
-#[serde(deny_unknown_fields)]
-pub struct EnergySpec {
-    pub min_soc_percent: f64,
-    pub reserve_endurance_s: f64,
-    pub max_power_w: f64,
-}
-
-#[derive(Debug, Deserialize)]
-#[serde(deny_unknown_fields)]
-pub struct GuaranteesSpec {
-    pub max_input_age_ms: u64,
-    pub max_tick_interval_ms: u64,
-    pub deadline_ms: u64,
-}
-
-#[derive(Debug, Deserialize)]
-#[serde(deny_unknown_fields)]
-pub struct InvariantsSpec {
-    pub min_altitude_m: f64,
-    pub max_bank_deg: f64,
-    pub require_geofence: bool,
-}
-
-#[derive(Debug, Deserialize)]
-#[serde(deny_unknown_fields)]
-pub struct ProfileSpec {
-    pub name: String,
-    pub timing: ProfileTiming,
-    pub capabilities: ProfileCapabilities,
-}
-
-#[derive(Debug, Deserialize)]
-#[serde(deny_unknown_fields)]
-pub struct ProfileTiming {
-    pub control_hz: u64,
-    pub deadline_ms: u64,
-}
-
-#[derive(Debug, Deserialize)]
-#[serde(deny_unknown_fields)]
-pub struct ProfileCapabilities {
-    pub vtol: bool,
-    pub fixed_wing: bool,
-    pub max_payload_kg: f64,
+pub struct AuditSchemaSpec {
+    pub schema_version: u32,
+    pub fields: Vec<String>,
 }
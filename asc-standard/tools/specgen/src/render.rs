@@ -14,9 +14,19 @@ pub fn render_reason_codes(tuple: &TupleSpec) -> String {
         .collect::<Vec<_>>()
         .join("\n");
 
+    let all_reasons = tuple
+        .reason_codes
+        .iter()
+        .map(|r| format!("        Self::{},", r))
+        .collect::<Vec<_>>()
+        .join("\n");
+
     format!(
-        "// @generated by tools/specgen. DO NOT EDIT.\n\nuse serde::{{Deserialize, Serialize}};\n\n#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]\npub enum Severity {{\n{}\n}}\n\n#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]\npub enum ReasonCode {{\n{}\n}}\n",
-        severity_variants, reason_variants
+        "// @generated by tools/specgen. DO NOT EDIT.\n\nuse serde::{{Deserialize, Serialize}};\n\n#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]\npub enum Severity {{\n{}\n}}\n\n#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]\npub enum ReasonCode {{\n{}\n}}\n\nimpl ReasonCode {{\n    pub const ALL: [Self; {}] = [\n{}\n    ];\n}}\n",
+        severity_variants,
+        reason_variants,
+        tuple.reason_codes.len(),
+        all_reasons
     )
 }
 
@@ -24,33 +34,288 @@ pub fn render_thresholds(
     state: &StateSpec,
     flow: &FlowSpec,
     energy: &EnergySpec,
-    guarantees: &GuaranteesSpec,
-    inv: &InvariantsSpec,
+    guarantees: &Guarantees,
+    inv: &Invariants,
+    interlock: &InterlockSpec,
+    deescalation: &DeescalationSpec,
 ) -> String {
     format!(
-        "// @generated by tools/specgen. DO NOT EDIT.\n\npub const FRAME: &str = \"{}\";\npub const MAX_SPEED_MPS: f64 = {:?};\npub const MAX_BANK_DEG: f64 = {:?};\npub const MIN_ALTITUDE_M: f64 = {:?};\npub const MIN_SOC_PERCENT: f64 = {:?};\npub const MAX_INPUT_AGE_MS: u64 = {};\npub const MAX_TICK_INTERVAL_MS: u64 = {};\npub const DEADLINE_MS: u64 = {};\npub const MAX_ROLL_RATE_DPS: f64 = {:?};\npub const MAX_PITCH_RATE_DPS: f64 = {:?};\npub const MAX_YAW_RATE_DPS: f64 = {:?};\npub const MAX_CLIMB_RATE_MPS: f64 = {:?};\n",
+        "// @generated by tools/specgen. DO NOT EDIT.\n\nuse asc_types::{{model::FlightPhase, Verdict}};\n\nuse crate::limits::PhaseLimits;\n\npub const FRAME: &str = \"{}\";\npub const POSITION_MIN_M: [f64; 3] = {:?};\npub const POSITION_MAX_M: [f64; 3] = {:?};\npub const POSITION_BOUNDS_VERDICT: Verdict = Verdict::{:?};\npub const ATTITUDE_LIMIT_DEG: f64 = {:?};\npub const MAX_SPEED_MPS: f64 = {:?};\npub const MAX_BANK_DEG: f64 = {:?};\npub const MIN_ALTITUDE_M: f64 = {:?};\npub const BARRIER_GAIN_PER_S: f64 = {:?};\npub const MIN_SOC_PERCENT: f64 = {:?};\npub const RESERVE_ENDURANCE_S: f64 = {:?};\npub const MAX_POWER_W: f64 = {:?};\npub const MAX_INPUT_AGE_MS: u64 = {};\npub const MAX_TICK_INTERVAL_MS: u64 = {};\npub const DEADLINE_MS: u64 = {};\npub const MAX_ROLL_RATE_DPS: f64 = {:?};\npub const MAX_PITCH_RATE_DPS: f64 = {:?};\npub const MAX_YAW_RATE_DPS: f64 = {:?};\npub const MAX_CLIMB_RATE_MPS: f64 = {:?};\npub const MAX_RATE_STEP_DPS: f64 = {:?};\npub const MAX_CLIMB_STEP_MPS: f64 = {:?};\npub const ARMED_REQUIRED: bool = {};\npub const FAULT_LATCHED_SHUTDOWN: bool = {};\npub const RESET_AUTHORITIES: &[&str] = &{};\npub const DEESCALATION_CLEAN_TICKS: u32 = {};\npub const DEESCALATION_MIN_DWELL_MS: u64 = {};\npub const SOC_EXIT_BAND_PERCENT: f64 = {:?};\npub const SPEED_EXIT_BAND_MPS: f64 = {:?};\n",
         state.frame,
+        state.position_bounds_m.min,
+        state.position_bounds_m.max,
+        state.position_bounds_verdict,
+        state.attitude_limit_deg,
         state.max_speed_mps,
         inv.max_bank_deg,
         inv.min_altitude_m,
+        inv.barrier_gain_per_s,
         energy.min_soc_percent,
+        energy.reserve_endurance_s,
+        energy.max_power_w,
         guarantees.max_input_age_ms,
         guarantees.max_tick_interval_ms,
         guarantees.deadline_ms,
         flow.max_roll_rate_dps,
         flow.max_pitch_rate_dps,
         flow.max_yaw_rate_dps,
-        flow.max_climb_rate_mps
+        flow.max_climb_rate_mps,
+        flow.max_rate_step_dps,
+        flow.max_climb_step_mps,
+        interlock.armed_required,
+        interlock.fault_latched_shutdown,
+        array(
+            &interlock
+                .reset_authorities
+                .iter()
+                .map(|a| format!("{a:?}"))
+                .collect::<Vec<_>>(),
+            "",
+            "pub const RESET_AUTHORITIES: &[&str] = &".len(),
+            1
+        ),
+        deescalation.clean_ticks,
+        deescalation.min_dwell_ms,
+        deescalation.exit_bands.soc_percent,
+        deescalation.exit_bands.speed_mps
     )
 }
 
-pub fn render_profile(profile: &ProfileSpec) -> String {
+pub fn render_profile(profile: &Profile, geofence: Option<&GeofenceSpec>) -> String {
     format!(
-        "// @generated by tools/specgen. DO NOT EDIT.\n\npub const PROFILE_NAME: &str = \"{}\";\npub const CONTROL_HZ: u64 = {};\npub const PROFILE_DEADLINE_MS: u64 = {};\npub const CAP_VTOL: bool = {};\npub const CAP_FIXED_WING: bool = {};\n",
+        "// @generated by tools/specgen. DO NOT EDIT.\n\nuse crate::geofence::{};\nuse crate::transition::{};\n\npub const PROFILE_NAME: &str = \"{}\";\npub const CONTROL_HZ: u64 = {};\npub const PROFILE_DEADLINE_MS: u64 = {};\npub const CAP_VTOL: bool = {};\npub const CAP_FIXED_WING: bool = {};\npub const CAP_MAX_PAYLOAD_KG: f64 = {:?};\npub const CAP_MIN_AIRSPEED_MPS: Option<f64> = {:?};\npub const CAP_MAX_HOVER_DESCENT_MPS: Option<f64> = {:?};\n",
+        if geofence.is_some() {
+            "{BakedGeofence, BakedShape, BakedZone}"
+        } else {
+            "BakedGeofence"
+        },
+        if profile.transition.is_some() {
+            "{ModeLimits, TransitionLimits}"
+        } else {
+            "TransitionLimits"
+        },
         profile.name,
         profile.timing.control_hz,
         profile.timing.deadline_ms,
         profile.capabilities.vtol,
-        profile.capabilities.fixed_wing
+        profile.capabilities.fixed_wing,
+        profile.capabilities.max_payload_kg,
+        profile.capabilities.min_airspeed_mps,
+        profile.capabilities.max_hover_descent_mps
+    ) + &render_transition(profile)
+        + &render_geofence(geofence)
+}
+
+fn render_geofence(geofence: Option<&GeofenceSpec>) -> String {
+    let Some(fence) = geofence else {
+        return "pub const GEOFENCE: Option<BakedGeofence> = None;\n".to_string();
+    };
+    let zone = |zone: &FenceZoneSpec, indent: &str| {
+        let shape = match &zone.shape {
+            FenceShapeSpec::Polygon { vertices_m } => {
+                let vertices = vertices_m
+                    .iter()
+                    .map(|v| format!("{v:?}"))
+                    .collect::<Vec<_>>();
+                let prefix = format!("{indent}    shape: BakedShape::Polygon(&");
+                format!(
+                    "BakedShape::Polygon(&{})",
+                    array(&vertices, &format!("{indent}    "), prefix.len(), 2)
+                )
+            }
+            FenceShapeSpec::Cylinder { center_m, radius_m } => format!(
+                "BakedShape::Cylinder {{\n{indent}        center_m: {center_m:?},\n{indent}        radius_m: {radius_m:?},\n{indent}    }}"
+            ),
+        };
+        format!(
+            "BakedZone {{\n{indent}    id: {:?},\n{indent}    shape: {shape},\n{indent}    floor_m: {:?},\n{indent}    ceiling_m: {:?},\n{indent}}}",
+            zone.id, zone.floor_m, zone.ceiling_m
+        )
+    };
+    let exclusions = fence
+        .exclusions
+        .iter()
+        .map(|z| zone(z, "        "))
+        .collect::<Vec<_>>();
+    format!(
+        "pub const GEOFENCE: Option<BakedGeofence> = Some(BakedGeofence {{\n    inclusion: {},\n    exclusions: &{},\n}});\n",
+        zone(&fence.inclusion, "    "),
+        array(&exclusions, "    ", "    exclusions: &".len(), 1)
+    )
+}
+
+fn render_transition(profile: &Profile) -> String {
+    let Some(transition) = &profile.transition else {
+        return "pub const TRANSITION: Option<TransitionLimits> = None;\n".to_string();
+    };
+    let mode = |limits: &ModeLimitsSpec| {
+        format!(
+            "ModeLimits {{\n        max_bank_deg: {:?},\n        max_roll_rate_dps: {:?},\n        max_pitch_rate_dps: {:?},\n        max_yaw_rate_dps: {:?},\n        max_climb_rate_mps: {:?},\n    }}",
+            limits.max_bank_deg,
+            limits.max_roll_rate_dps,
+            limits.max_pitch_rate_dps,
+            limits.max_yaw_rate_dps,
+            limits.max_climb_rate_mps
+        )
+    };
+    format!(
+        "pub const TRANSITION: Option<TransitionLimits> = Some(TransitionLimits {{\n    hover_airspeed_mps: {:?},\n    forward_airspeed_mps: {:?},\n    hover: {},\n    forward: {},\n}});\n",
+        transition.hover_airspeed_mps,
+        profile.capabilities.min_airspeed_mps.unwrap_or_default(),
+        mode(&transition.hover),
+        mode(&transition.forward)
+    )
+}
+
+pub fn render_fallbacks(fallbacks: &Fallbacks) -> String {
+    format!(
+        "pub const HOLD_RATES_DPS: [f64; 3] = {:?};\npub const HOLD_CLIMB_MPS: f64 = {:?};\npub const OVERRIDE_RATES_DPS: [f64; 3] = {:?};\npub const OVERRIDE_CLIMB_MPS: f64 = {:?};\npub const SHUTDOWN_RATES_DPS: [f64; 3] = {:?};\npub const SHUTDOWN_CLIMB_MPS: f64 = {:?};\npub const LOITER_RATES_DPS: [f64; 3] = {:?};\npub const LOITER_CLIMB_MPS: f64 = {:?};\npub const STALL_RATES_DPS: [f64; 3] = {:?};\npub const STALL_CLIMB_MPS: f64 = {:?};\n",
+        fallbacks.hold.rates_dps,
+        fallbacks.hold.climb_mps,
+        fallbacks.r#override.rates_dps,
+        fallbacks.r#override.climb_mps,
+        fallbacks.shutdown.rates_dps,
+        fallbacks.shutdown.climb_mps,
+        fallbacks.loiter.rates_dps,
+        fallbacks.loiter.climb_mps,
+        fallbacks.stall.rates_dps,
+        fallbacks.stall.climb_mps
     )
 }
+
+pub fn render_formulas(guarantees: &Guarantees) -> String {
+    let prefix = "pub const STL_FORMULAS: &[(&str, &str)] = &";
+    let entries = guarantees
+        .formulas
+        .iter()
+        .map(|f| format!("({:?}, {:?})", f.id, f.formula))
+        .collect::<Vec<_>>();
+    format!("{prefix}{};\n", array(&entries, "", prefix.len(), 1))
+}
+
+// Phases are keyed by the kernel's FlightPhase, so they are emitted in its
+// declaration order, the same ordering asc-contract's maps give.
+pub fn render_phases(phases: &PhasesSpec) -> String {
+    let transitions = phases
+        .transitions
+        .iter()
+        .flat_map(|(from, targets)| {
+            targets
+                .iter()
+                .map(move |to| format!("(FlightPhase::{from:?}, FlightPhase::{to:?})"))
+        })
+        .collect::<Vec<_>>();
+    let overrides = phases
+        .overrides
+        .iter()
+        .map(|(phase, o)| {
+            let inv = o.invariants.as_ref();
+            let flow = o.flow.as_ref();
+            format!(
+                "PhaseLimits {{\n        phase: FlightPhase::{phase:?},\n        min_altitude_m: {:?},\n        max_bank_deg: {:?},\n        max_roll_rate_dps: {:?},\n        max_pitch_rate_dps: {:?},\n        max_yaw_rate_dps: {:?},\n        max_climb_rate_mps: {:?},\n    }}",
+                inv.and_then(|i| i.min_altitude_m),
+                inv.and_then(|i| i.max_bank_deg),
+                flow.and_then(|f| f.max_roll_rate_dps),
+                flow.and_then(|f| f.max_pitch_rate_dps),
+                flow.and_then(|f| f.max_yaw_rate_dps),
+                flow.and_then(|f| f.max_climb_rate_mps),
+            )
+        })
+        .collect::<Vec<_>>();
+    let transitions_prefix = "pub const PHASE_TRANSITIONS: &[(FlightPhase, FlightPhase)] = &";
+    let overrides_prefix = "pub const PHASE_OVERRIDES: &[PhaseLimits] = &";
+    format!(
+        "{transitions_prefix}{};\n{overrides_prefix}{};\n",
+        array(&transitions, "", transitions_prefix.len(), 1),
+        array(&overrides, "", overrides_prefix.len(), 1)
+    )
+}
+
+// Every record leads with the schema version, followed by the declared fields
+// in declaration order.
+pub fn render_audit(audit: &AuditSchemaSpec) -> String {
+    let declared = audit
+        .fields
+        .iter()
+        .filter_map(|field| AUDIT_FIELDS.iter().find(|(name, ..)| name == field))
+        .collect::<Vec<_>>();
+    // Sorted as rustfmt orders them, modules before types.
+    let mut paths = declared
+        .iter()
+        .filter_map(|(_, _, path, _)| *path)
+        .collect::<Vec<_>>();
+    paths.sort_by_key(|path| {
+        path.split("::")
+            .map(|segment| (segment.starts_with(char::is_uppercase), segment))
+            .collect::<Vec<_>>()
+    });
+    paths.dedup();
+    let imports = paths
+        .iter()
+        .map(|path| format!("use {path};\n"))
+        .collect::<String>();
+    let names_prefix = format!("pub const AUDIT_FIELDS: [&str; {}] = ", declared.len());
+    let names = array(
+        &declared
+            .iter()
+            .map(|(name, ..)| format!("{name:?}"))
+            .collect::<Vec<_>>(),
+        "",
+        names_prefix.len(),
+        1,
+    );
+    let members = declared
+        .iter()
+        .map(|(name, ty, ..)| format!("    pub {name}: {ty},\n"))
+        .collect::<String>();
+    let values = declared
+        .iter()
+        .map(|(name, .., value)| format!("            {name}: {value},\n"))
+        .collect::<String>();
+    // Parameters no declared field reads are prefixed to keep them unused.
+    let param = |name: &str| {
+        if declared.iter().any(|(.., value)| value.starts_with(name)) {
+            name.to_string()
+        } else {
+            format!("_{name}")
+        }
+    };
+    format!(
+        "// @generated by tools/specgen. DO NOT EDIT.\n\n{imports}use serde::{{Deserialize, Serialize}};\n\nuse crate::Evaluation;\n\npub const AUDIT_SCHEMA_VERSION: u32 = {};\n{names_prefix}{names};\n\n#[derive(Debug, Clone, Serialize, Deserialize)]\npub struct AuditRecord {{\n    pub schema_version: u32,\n{members}}}\n\nimpl AuditRecord {{\n    pub fn new({}: &Evaluation, {}: &str) -> Self {{\n        Self {{\n            schema_version: AUDIT_SCHEMA_VERSION,\n{values}        }}\n    }}\n}}\n",
+        audit.schema_version,
+        param("evaluation"),
+        param("tip_hash")
+    )
+}
+
+const MAX_WIDTH: usize = 100;
+// rustfmt's default `array_width`, 60% of `max_width`.
+const ARRAY_WIDTH: usize = 60;
+
+// Lays out an array literal the way rustfmt does, so generated files pass
+// `cargo fmt --check` as written: `[]` when empty, on one line when it fits,
+// a lone block item (e.g. a struct literal) hugging the brackets, and
+// otherwise one item per line. Items are rendered for the one-per-line layout,
+// at `indent` plus four spaces. `column` is where the `[` starts and `tail` the
+// width of whatever follows the `]` on its line.
+fn array(items: &[String], indent: &str, column: usize, tail: usize) -> String {
+    if items.is_empty() {
+        return "[]".to_string();
+    }
+    let line = format!("[{}]", items.join(", "));
+    if !line.contains('\n') && line.len() <= ARRAY_WIDTH && column + line.len() + tail <= MAX_WIDTH
+    {
+        return line;
+    }
+    if let [item] = items {
+        if item.ends_with('}') && item.contains('\n') {
+            let outdented = item.replace("\n    ", "\n");
+            return format!("[{outdented}]");
+        }
+    }
+    let lines = items
+        .iter()
+        .map(|item| format!("{indent}    {item},\n"))
+        .collect::<String>();
+    format!("[\n{lines}{indent}]")
+}